@@ -0,0 +1,429 @@
+//! Frame recorder for step-by-step simulations.
+//!
+//! A simulation calls [`Recorder::record`] once per step with a closure
+//! rendering the current state as a grid of coloured cells. The closure is
+//! only called for the steps that are actually kept, so a disabled recorder
+//! costs a branch per step.
+//!
+//! Steps are kept if they are in the `--frames start..end` range and if the
+//! frame-rate limit allows it: the simulation is assumed to run at `--speed`
+//! steps per second (by default, the frame rate), and at most `--fps` frames
+//! are kept per second. For instance, `--speed 1000 --fps 25` keeps one step
+//! out of 40.
+
+use std::io::Write;
+
+use crate::{Result, Matrix2D, args::{Args, IndexRange}};
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+
+pub const WHITE: Color = [255, 255, 255];
+
+/// Returns a colour for `index`, such that consecutive indices get
+/// distinguishable colours.
+pub fn palette_color(index: usize) -> Color {
+    let hue = (index as f64 * 0.618_033_988_75).fract() * 6.;
+    let x = 1. - (hue % 2. - 1.).abs();
+    let (r, g, b) =
+        match hue as usize {
+            0 => (1., x, 0.),
+            1 => (x, 1., 0.),
+            2 => (0., 1., x),
+            3 => (0., x, 1.),
+            4 => (x, 0., 1.),
+            _ => (1., 0., x),
+        };
+    [r, g, b].map(|c| (64. + c * 191.) as u8)
+}
+
+pub struct Frame {
+    pub cells: Matrix2D<Color>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self { cells: vec![vec![background; width]; height] }
+    }
+
+    pub fn from_matrix<T>(mat: &Matrix2D<T>, color: impl Fn(&T) -> Color) -> Self {
+        Self {
+            cells: mat.iter().map(|line| line.iter().map(&color).collect())
+                .collect()
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |line| line.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|line| line.get_mut(x)) {
+            *cell = color
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format { Ppm, Svg, Gif }
+
+impl std::str::FromStr for Format {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ppm" => Ok(Format::Ppm),
+            "svg" => Ok(Format::Svg),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!("Unknown format: {s}"))?,
+        }
+    }
+}
+
+pub struct RecordOptions {
+    /// Directory for numbered frames, or file for an animated GIF.
+    pub path: std::path::PathBuf,
+    pub format: Format,
+    pub frames: IndexRange,
+    pub fps: u64,
+    pub speed: u64,
+    /// Size in pixels of a cell.
+    pub scale: usize,
+}
+
+impl RecordOptions {
+    pub fn from_args(args: &mut Args) -> Result<Option<Self>> {
+        let Some(path) = args.value("--record")? else { return Ok(None) };
+        let path = std::path::PathBuf::from(path);
+        let format =
+            match args.parse("--format")? {
+                Some(format) => format,
+                None if path.extension().is_some_and(|ext| ext == "gif") =>
+                    Format::Gif,
+                None => Format::Ppm,
+            };
+        let frames = args.parse("--frames")?.unwrap_or(IndexRange::ALL);
+        let fps = args.parse("--fps")?.unwrap_or(25);
+        let speed = args.parse("--speed")?.unwrap_or(fps);
+        let scale = args.parse("--scale")?.unwrap_or(4);
+        if fps == 0 || speed == 0 || scale == 0 {
+            Err("--fps, --speed and --scale should be positive")?
+        }
+        Ok(Some(Self { path, format, frames, fps, speed, scale }))
+    }
+}
+
+enum Output {
+    Files,
+    Gif(std::io::BufWriter<std::fs::File>),
+}
+
+struct Recording {
+    options: RecordOptions,
+    output: Option<Output>,
+    step: usize,
+    last_slot: Option<u128>,
+    written: usize,
+    size: Option<(usize, usize)>,
+    error: Option<crate::Error>,
+}
+
+pub struct Recorder(Option<Recording>);
+
+impl Recorder {
+    pub fn disabled() -> Self {
+        Self(None)
+    }
+
+    pub fn new(options: RecordOptions) -> Self {
+        Self(Some(Recording {
+            options, output: None, step: 0, last_slot: None, written: 0,
+            size: None, error: None,
+        }))
+    }
+
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        Ok(match RecordOptions::from_args(args)? {
+            None => Self::disabled(),
+            Some(options) => Self::new(options),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Records a simulation step. Errors are deferred to [`Recorder::finish`].
+    pub fn record(&mut self, render: impl FnOnce() -> Frame) {
+        let Some(recording) = &mut self.0 else { return };
+        let step = recording.step;
+        recording.step += 1;
+        if recording.error.is_some() || !recording.options.frames.contains(step) {
+            return;
+        }
+        let slot =
+            step as u128 * recording.options.fps as u128
+            / recording.options.speed as u128;
+        if recording.last_slot == Some(slot) {
+            return;
+        }
+        recording.last_slot = Some(slot);
+        if let Err(error) = recording.write(&render()) {
+            recording.error = Some(error);
+        }
+    }
+
+    /// Terminates the recording and returns the number of frames written.
+    pub fn finish(self) -> Result<usize> {
+        let Some(mut recording) = self.0 else { return Ok(0) };
+        if let Some(error) = recording.error {
+            return Err(error);
+        }
+        if let Some(Output::Gif(mut writer)) = recording.output.take() {
+            writer.write_all(&[GIF_TRAILER])?;
+            writer.flush()?;
+        }
+        Ok(recording.written)
+    }
+}
+
+impl Recording {
+    fn write(&mut self, frame: &Frame) -> Result<()> {
+        let size = (frame.width(), frame.height());
+        if size.0 == 0 || size.1 == 0 {
+            Err("Empty frame")?
+        }
+        match self.size {
+            None => self.size = Some(size),
+            Some(previous) if previous != size => Err("Frame size changed")?,
+            Some(_) => (),
+        }
+        let options = &self.options;
+        if self.output.is_none() {
+            self.output = Some(
+                match options.format {
+                    Format::Ppm | Format::Svg => {
+                        std::fs::create_dir_all(&options.path)?;
+                        Output::Files
+                    }
+                    Format::Gif => {
+                        let file = std::fs::File::create(&options.path)?;
+                        let mut writer = std::io::BufWriter::new(file);
+                        write_gif_header(
+                            &mut writer, size.0 * options.scale,
+                            size.1 * options.scale
+                        )?;
+                        Output::Gif(writer)
+                    }
+                }
+            );
+        }
+        match (&mut self.output, options.format) {
+            (Some(Output::Gif(writer)), _) => {
+                let delay = (100 / options.fps).max(1);
+                write_gif_frame(writer, frame, options.scale, delay)?
+            }
+            (_, format) => {
+                let extension = if format == Format::Svg { "svg" } else { "ppm" };
+                let path = options.path.join(
+                    format!("frame-{:06}.{extension}", self.written));
+                let mut writer =
+                    std::io::BufWriter::new(std::fs::File::create(path)?);
+                if format == Format::Svg {
+                    write_svg(&mut writer, frame, options.scale)?
+                }
+                else {
+                    write_ppm(&mut writer, frame, options.scale)?
+                }
+                writer.flush()?
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+fn write_ppm(writer: &mut impl Write, frame: &Frame, scale: usize) -> Result<()> {
+    write!(
+        writer, "P6\n{} {}\n255\n", frame.width() * scale, frame.height() * scale
+    )?;
+    for line in &frame.cells {
+        let row: Vec<u8> =
+            line.iter().flat_map(|color| std::iter::repeat_n(color, scale))
+            .flatten().cloned().collect();
+        for _ in 0 .. scale {
+            writer.write_all(&row)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_svg(writer: &mut impl Write, frame: &Frame, scale: usize) -> Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" \
+         height=\"{height}\" shape-rendering=\"crispEdges\">"
+    )?;
+    for (y, line) in frame.cells.iter().enumerate() {
+        let mut x = 0;
+        while x < line.len() {
+            let color = line[x];
+            let run =
+                line[x ..].iter().take_while(|&&other| other == color).count();
+            let [r, g, b] = color;
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{scale}\" \
+                 fill=\"#{r:02x}{g:02x}{b:02x}\"/>",
+                x * scale, y * scale, run * scale
+            )?;
+            x += run;
+        }
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
+
+const GIF_TRAILER: u8 = 0x3b;
+
+fn write_gif_header(
+    writer: &mut impl Write, width: usize, height: usize
+) -> Result<()> {
+    let width: u16 = width.try_into()?;
+    let height: u16 = height.try_into()?;
+    writer.write_all(b"GIF89a")?;
+    writer.write_all(&width.to_le_bytes())?;
+    writer.write_all(&height.to_le_bytes())?;
+    // No global colour table: each frame comes with its own palette.
+    writer.write_all(&[0, 0, 0])?;
+    // Loop forever.
+    writer.write_all(&[0x21, 0xff, 0x0b])?;
+    writer.write_all(b"NETSCAPE2.0")?;
+    writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+    Ok(())
+}
+
+fn write_gif_frame(
+    writer: &mut impl Write, frame: &Frame, scale: usize, delay: u64
+) -> Result<()> {
+    let mut palette: Vec<Color> = Vec::new();
+    let mut index_of = std::collections::HashMap::new();
+    let mut indices = Vec::with_capacity(frame.width() * frame.height());
+    for line in &frame.cells {
+        for _ in 0 .. scale {
+            for color in line {
+                let index = match index_of.get(color) {
+                    Some(&index) => index,
+                    None => {
+                        if palette.len() == 256 {
+                            Err("More than 256 colours in a GIF frame")?
+                        }
+                        let index = palette.len() as u8;
+                        palette.push(*color);
+                        index_of.insert(*color, index);
+                        index
+                    }
+                };
+                indices.extend(std::iter::repeat_n(index, scale));
+            }
+        }
+    }
+    let table_bits =
+        (1 ..= 8).find(|bits| palette.len() <= 1 << bits).unwrap_or(8);
+    palette.resize(1 << table_bits, BLACK);
+    let delay: u16 = delay.try_into()?;
+    writer.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+    writer.write_all(&delay.to_le_bytes())?;
+    writer.write_all(&[0x00, 0x00])?;
+    let width: u16 = (frame.width() * scale).try_into()?;
+    let height: u16 = (frame.height() * scale).try_into()?;
+    writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+    writer.write_all(&width.to_le_bytes())?;
+    writer.write_all(&height.to_le_bytes())?;
+    writer.write_all(&[0x80 | (table_bits - 1) as u8])?;
+    for color in &palette {
+        writer.write_all(color)?;
+    }
+    let min_code_size = table_bits.max(2) as u8;
+    writer.write_all(&[min_code_size])?;
+    for block in lzw_encode(&indices, min_code_size).chunks(255) {
+        writer.write_all(&[block.len() as u8])?;
+        writer.write_all(block)?;
+    }
+    writer.write_all(&[0])?;
+    Ok(())
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    accu: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn push(&mut self, code: u16, size: u32) {
+        self.accu |= (code as u32) << self.len;
+        self.len += size;
+        while self.len >= 8 {
+            self.bytes.push(self.accu as u8);
+            self.accu >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.accu as u8);
+        }
+        self.bytes
+    }
+}
+
+const LZW_MAX_CODE: u16 = 4096;
+
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end_of_information = clear + 1;
+    let mut output = BitWriter { bytes: Vec::new(), accu: 0, len: 0 };
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next_code = end_of_information + 1;
+    let mut dictionary: std::collections::HashMap<(u16, u8), u16> =
+        std::collections::HashMap::new();
+    output.push(clear, code_size);
+    let mut iter = indices.iter();
+    let Some(&first) = iter.next() else {
+        output.push(end_of_information, code_size);
+        return output.finish();
+    };
+    let mut prefix = first as u16;
+    for &index in iter {
+        if let Some(&code) = dictionary.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        output.push(prefix, code_size);
+        if next_code < LZW_MAX_CODE {
+            dictionary.insert((prefix, index), next_code);
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            next_code += 1;
+        }
+        else {
+            output.push(clear, code_size);
+            dictionary.clear();
+            code_size = min_code_size as u32 + 1;
+            next_code = end_of_information + 1;
+        }
+        prefix = index as u16;
+    }
+    output.push(prefix, code_size);
+    output.push(end_of_information, code_size);
+    output.finish()
+}
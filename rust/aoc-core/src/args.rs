@@ -0,0 +1,98 @@
+use crate::{Error, Result};
+
+/// Command-line arguments, consumed option by option.
+///
+/// Options are written `--name value` or `--name=value`, flags are written
+/// `--name`. Whatever is left once all the options have been taken are the
+/// positional arguments.
+pub struct Args(Vec<String>);
+
+impl Args {
+    pub fn from_env() -> Self {
+        Self(std::env::args().skip(1).collect())
+    }
+
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Self(args.into_iter().collect())
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.0.iter().position(|arg| arg == name) {
+            None => false,
+            Some(index) => {
+                self.0.remove(index);
+                true
+            }
+        }
+    }
+
+    pub fn value(&mut self, name: &str) -> Result<Option<String>> {
+        let prefix = format!("{name}=");
+        for index in 0 .. self.0.len() {
+            if let Some(value) = self.0[index].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.0.remove(index);
+                return Ok(Some(value));
+            }
+            if self.0[index] == name {
+                if index + 1 >= self.0.len() {
+                    Err(format!("Missing value for {name}"))?
+                }
+                let value = self.0.remove(index + 1);
+                self.0.remove(index);
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn parse<T: std::str::FromStr>(&mut self, name: &str) -> Result<Option<T>>
+    where Error: From<T::Err> {
+        match self.value(name)? {
+            None => Ok(None),
+            Some(value) =>
+                Ok(Some(value.parse().map_err(|e: T::Err| {
+                    let Error(message) = e.into();
+                    format!("Invalid value for {name}: {message}")
+                })?)),
+        }
+    }
+
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self.0.iter().position(|arg| !arg.starts_with("--"))?;
+        Some(self.0.remove(index))
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self.0.first() {
+            None => Ok(()),
+            Some(arg) => Err(format!("Unexpected argument {arg}"))?,
+        }
+    }
+}
+
+/// Range of indices written `start..end`, where both bounds are optional.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexRange {
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+impl IndexRange {
+    pub const ALL: Self = Self { start: 0, end: None };
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.start <= index && self.end.is_none_or(|end| index < end)
+    }
+}
+
+impl std::str::FromStr for IndexRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (start_str, end_str) = s.split_once("..").ok_or("'..' expected")?;
+        let start = if start_str.is_empty() { 0 } else { start_str.parse()? };
+        let end = if end_str.is_empty() { None } else { Some(end_str.parse()?) };
+        Ok(Self { start, end })
+    }
+}
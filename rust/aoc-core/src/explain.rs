@@ -0,0 +1,234 @@
+//! Per-line explanations (`--explain`): each solver supporting it gives one
+//! row of named facts per line (or per block) of the input, printed as an
+//! aligned table or as JSON lines.
+
+use crate::{Error, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format {s}, expected table or json"))?,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Number(i128),
+    Bool(bool),
+    Text(String),
+    List(Vec<Value>),
+}
+
+impl From<u32> for Value {
+    fn from(n: u32) -> Self {
+        Value::Number(n as i128)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(n: u64) -> Self {
+        Value::Number(n as i128)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Number(n as i128)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Number(n as i128)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        Value::Text(c.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Text(s)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Value {
+    fn write_text(&self, out: &mut String, nested: bool) {
+        match self {
+            Value::Number(n) => out.push_str(&n.to_string()),
+            Value::Bool(b) => out.push_str(if *b { "yes" } else { "no" }),
+            Value::Text(s) => out.push_str(s),
+            Value::List(values) => {
+                if nested {
+                    out.push('[');
+                }
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push(' ');
+                    }
+                    value.write_text(out, true);
+                }
+                if nested {
+                    out.push(']');
+                }
+            }
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Value::Number(n) => out.push_str(&n.to_string()),
+            Value::Bool(b) => out.push_str(&b.to_string()),
+            Value::Text(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        c if (c as u32) < 0x20 =>
+                            out.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            Value::List(values) => {
+                out.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    value.write_json(out);
+                }
+                out.push(']');
+            }
+        }
+    }
+}
+
+/// Named facts, in the order they are printed.
+#[derive(Clone, Default)]
+pub struct Row(Vec<(&'static str, Value)>);
+
+impl Row {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &'static str, value: impl Into<Value>) {
+        let value = value.into();
+        match self.0.iter_mut().find(|(field, _)| *field == name) {
+            Some((_, old)) => *old = value,
+            None => self.0.push((name, value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.iter().find(|(field, _)| *field == name).map(|(_, value)| value)
+    }
+}
+
+/// Prints the rows, numbered from 1 in a first column called `index`.
+pub fn print(
+    out: &mut impl std::io::Write, format: Format, index: &'static str,
+    rows: &[Row]
+) -> Result<()> {
+    let rows: Vec<Row> = rows.iter().enumerate().map(|(number, row)| {
+        let mut fields = vec![(index, Value::from(number + 1))];
+        fields.extend(row.0.iter().cloned());
+        Row(fields)
+    }).collect();
+    match format {
+        Format::Json =>
+            for row in &rows {
+                let mut line = String::from("{");
+                for (number, (name, value)) in row.0.iter().enumerate() {
+                    if number > 0 {
+                        line.push_str(", ");
+                    }
+                    Value::from(*name).write_json(&mut line);
+                    line.push_str(": ");
+                    value.write_json(&mut line);
+                }
+                line.push('}');
+                writeln!(out, "{line}")?;
+            }
+        Format::Table => {
+            let mut columns: Vec<&'static str> = Vec::new();
+            for row in &rows {
+                for (name, _) in &row.0 {
+                    if !columns.contains(name) {
+                        columns.push(name);
+                    }
+                }
+            }
+            let cells: Vec<Vec<(String, bool)>> = rows.iter().map(|row|
+                columns.iter().map(|column| {
+                    let mut text = String::new();
+                    let value = row.get(column);
+                    if let Some(value) = value {
+                        value.write_text(&mut text, false);
+                    }
+                    (text, matches!(value, Some(Value::Number(_))))
+                }).collect()
+            ).collect();
+            let widths: Vec<usize> = columns.iter().enumerate().map(|(i, column)|
+                cells.iter().map(|row| row[i].0.chars().count())
+                    .chain(std::iter::once(column.len())).max().unwrap_or(0)
+            ).collect();
+            let header: Vec<String> = columns.iter().zip(&widths)
+                .map(|(column, width)| format!("{column:<width$}")).collect();
+            writeln!(out, "{}", header.join("  ").trim_end())?;
+            for row in cells {
+                let line: Vec<String> = row.iter().zip(&widths)
+                    .map(|((text, number), width)|
+                        if *number {
+                            format!("{text:>width$}")
+                        }
+                        else {
+                            format!("{text:<width$}")
+                        }
+                    ).collect();
+                writeln!(out, "{}", line.join("  ").trim_end())?;
+            }
+        }
+    }
+    Ok(())
+}
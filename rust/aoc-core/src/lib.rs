@@ -1,4 +1,20 @@
-pub struct Error(String);
+pub mod args;
+
+pub mod animation;
+
+pub mod rng;
+
+pub mod parallel;
+
+pub mod solver;
+
+pub mod explain;
+
+pub mod trace;
+
+pub mod memory;
+
+pub struct Error(pub String);
 
 impl<T: ToString> From<T> for Error {
     fn from(x: T) -> Self {
@@ -16,9 +32,10 @@ impl std::fmt::Debug for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Name(usize);
 
+#[derive(Default)]
 pub struct NameTable(
     std::collections::HashMap<String, Name>,
     std::collections::HashMap<Name, String>,
@@ -55,7 +72,7 @@ pub type NameSet = std::collections::HashSet<Name>;
 pub type NameMap<T> = std::collections::HashMap<Name, T>;
 
 pub fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while a % b != 0 {
+    while !a.is_multiple_of(b) {
 	(a, b) = (b, a % b)
     }
     b
@@ -285,7 +302,7 @@ impl<T> Coords3D<T> {
 
 impl<T: std::str::FromStr> Coords3D<T>
 where T::Err: ToString {
-    pub fn parse<'a>(sep: &str, s: &str) -> Result<Self> {
+    pub fn parse(sep: &str, s: &str) -> Result<Self> {
         let mut components = s.split(sep);
         let components_vec: Vec<_> = components.by_ref().take(3).collect();
         let coords_str =
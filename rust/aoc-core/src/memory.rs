@@ -0,0 +1,126 @@
+//! Allocation accounting, with the `alloc-stats` cargo feature.
+//!
+//! The feature installs a global allocator counting the allocations, the
+//! bytes allocated and the peak of the bytes live at once. Solvers split
+//! their work into phases with [`phase`], and the runner prints the counts of
+//! each phase with `aoc run <day> --alloc-stats`. Without the feature,
+//! [`phase`] does nothing.
+//!
+//! Only the runner reports the phases: the per-day binaries, going through
+//! [`solver::main`](crate::solver::main), record them but never call
+//! [`finish`].
+//!
+//! Counts are global: allocations made by other threads in the meantime are
+//! included.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::freed(layout.size());
+    }
+
+    /* A reallocation counts as a new allocation followed by the release of
+       the old one. */
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::allocated(new_size);
+            Self::freed(layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+pub struct PhaseStats {
+    pub name: &'static str,
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_live_bytes: usize,
+}
+
+struct Phases {
+    done: Vec<PhaseStats>,
+    /// Name of the current phase, with the counts when it started.
+    current: Option<(&'static str, usize, usize)>,
+}
+
+static PHASES: std::sync::Mutex<Phases> =
+    std::sync::Mutex::new(Phases { done: Vec::new(), current: None });
+
+fn close(phases: &mut Phases) {
+    if let Some((name, allocations, bytes)) = phases.current.take() {
+        phases.done.push(PhaseStats {
+            name,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - bytes,
+            peak_live_bytes: PEAK.load(Ordering::Relaxed),
+        });
+    }
+}
+
+/// Ends the current phase, if any, and starts a new one.
+pub fn phase(name: &'static str) {
+    if !ENABLED {
+        return;
+    }
+    let mut phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    close(&mut phases);
+    PEAK.store(LIVE.load(Ordering::Relaxed), Ordering::Relaxed);
+    phases.current = Some((
+        name, ALLOCATIONS.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed)
+    ));
+}
+
+/// Ends the current phase and returns the counts of all the phases.
+///
+/// Called by the runner for `aoc run <day> --alloc-stats`.
+pub fn finish() -> Vec<PhaseStats> {
+    let mut phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    close(&mut phases);
+    std::mem::take(&mut phases.done)
+}
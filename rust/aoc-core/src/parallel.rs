@@ -0,0 +1,37 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Applies `f` to each item, on up to `jobs` threads which take the items
+/// one at a time. The results are in the same order as the items, as for
+/// a sequential run.
+pub fn map<T: Sync, R: Send>(
+    jobs: usize, items: &[T], f: impl Fn(&T) -> R + Sync
+) -> Vec<R> {
+    if jobs <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0 .. jobs.min(items.len())).map(|_|
+            scope.spawn(|| {
+                let mut done = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else { break };
+                    done.push((index, f(item)));
+                }
+                done
+            })
+        ).collect();
+        for worker in workers {
+            match worker.join() {
+                Ok(done) =>
+                    for (index, result) in done {
+                        results[index] = Some(result);
+                    }
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+    });
+    results.into_iter().flatten().collect()
+}
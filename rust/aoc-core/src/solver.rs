@@ -0,0 +1,159 @@
+use std::io::{BufRead, Write};
+
+use crate::{Error, Result, args::Args, explain, parallel, trace};
+
+/// Settings shared by all the solvers.
+pub struct Context {
+    /// Number of threads a solver may use.
+    pub jobs: usize,
+    /// Whether line-oriented solvers print the result of each line as soon
+    /// as it is computed.
+    pub print_lines: bool,
+    /// Whether solvers print the facts each line contributes (see
+    /// `crate::explain`).
+    pub explain: Option<explain::Format>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self { jobs: 1, print_lines: false, explain: None }
+    }
+}
+
+impl Context {
+    /// Reads `--jobs N`, which defaults to the available parallelism,
+    /// `--print-lines` and `--explain[=table|json]`. Also configures the
+    /// tracing (see `crate::trace`).
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        trace::configure_from_args(args)?;
+        let jobs =
+            match args.parse("--jobs")? {
+                Some(0) => Err("--jobs should be positive")?,
+                Some(jobs) => jobs,
+                None => std::thread::available_parallelism()
+                    .map_or(1, std::num::NonZeroUsize::get),
+            };
+        let print_lines = args.flag("--print-lines");
+        let explain =
+            if args.flag("--explain") {
+                Some(explain::Format::Table)
+            }
+            else {
+                args.parse("--explain")?
+            };
+        Ok(Self { jobs, print_lines, explain })
+    }
+}
+
+pub struct Solution {
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+impl Solution {
+    pub fn new(part1: impl ToString, part2: impl ToString) -> Self {
+        Self { part1: part1.to_string(), part2: Some(part2.to_string()) }
+    }
+
+    pub fn part1(part1: impl ToString) -> Self {
+        Self { part1: part1.to_string(), part2: None }
+    }
+}
+
+impl std::fmt::Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Part 1: {}", self.part1)?;
+        if let Some(part2) = &self.part2 {
+            writeln!(f, "Part 2: {part2}")?;
+        }
+        Ok(())
+    }
+}
+
+pub type Input<'a> = Box<dyn std::io::BufRead + 'a>;
+
+pub trait Solver: Sync {
+    fn solve(&self, context: &Context, input: Input) -> Result<Solution>;
+}
+
+impl<F> Solver for F
+where F: Fn(&Context, Input) -> Result<Solution> + Sync {
+    fn solve(&self, context: &Context, input: Input) -> Result<Solution> {
+        self(context, input)
+    }
+}
+
+/// Solver for inputs made of independent lines. Each line gives a result,
+/// which is folded into the state as soon as it is computed, so that the
+/// memory used does not grow with the input.
+pub trait LineSolver: Sync {
+    type Line: std::fmt::Display + Send;
+    type State: Default;
+
+    fn line(&self, line: &str) -> Result<Self::Line>;
+
+    fn fold(&self, state: &mut Self::State, line: Self::Line) -> Result<()>;
+
+    fn finish(&self, state: Self::State) -> Result<Solution>;
+
+    /// Facts about a line for `--explain`, given the state before the line
+    /// is folded.
+    fn explain(&self, _state: &Self::State, _line: &Self::Line) -> explain::Row {
+        explain::Row::new()
+    }
+
+    /// Completes the facts once all the lines are folded.
+    fn complete_explanation(&self, _state: &Self::State, _rows: &mut [explain::Row]) {
+    }
+}
+
+/// Number of lines read at once per job: lines of a batch are solved in
+/// parallel, then folded in order.
+const BATCH_SIZE: usize = 1024;
+
+pub fn stream(
+    solver: &impl LineSolver, context: &Context, input: Input
+) -> Result<Solution> {
+    let mut state = Default::default();
+    let mut lines = input.lines();
+    let mut batch: Vec<String> = Vec::new();
+    let mut rows = Vec::new();
+    let mut stdout = std::io::stdout().lock();
+    let mut line_number = 0;
+    loop {
+        batch.clear();
+        for line in lines.by_ref().take(BATCH_SIZE * context.jobs) {
+            batch.push(line?);
+        }
+        if batch.is_empty() {
+            break;
+        }
+        for line in parallel::map(context.jobs, &batch, |line| solver.line(line)) {
+            line_number += 1;
+            let line = line.map_err(|Error(message)|
+                format!("Line {line_number}: {message}")
+            )?;
+            if context.print_lines {
+                writeln!(stdout, "{line}")?;
+            }
+            if context.explain.is_some() {
+                rows.push(solver.explain(&state, &line));
+            }
+            solver.fold(&mut state, line)?;
+        }
+    }
+    if let Some(format) = context.explain {
+        solver.complete_explanation(&state, &mut rows);
+        explain::print(&mut stdout, format, "line", &rows)?;
+    }
+    solver.finish(state)
+}
+
+/// Entry point of the per-day binaries: solves standard input.
+pub fn main(solver: &dyn Solver) -> Result<()> {
+    let mut args = Args::from_env();
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    print!("{}", solver.solve(&context, Box::new(std::io::stdin().lock()))?);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Tracing of the key steps of the solvers, written to standard error.
+//!
+//! Each message has a level and a target, the day which emits it. `-v`
+//! shows the debug messages, `-vv` the trace messages as well, and
+//! `--trace-targets ac17,ac25` only shows the messages of these days.
+//!
+//! Messages are built by closures, which are only called when the message is
+//! shown, so disabled tracing costs an atomic load per call site.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::{Result, args::Args};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug = 1,
+    Trace = 2,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+/// Most verbose level shown, 0 if tracing is disabled.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Targets shown, all of them if `None`.
+static TARGETS: std::sync::RwLock<Option<Vec<String>>> =
+    std::sync::RwLock::new(None);
+
+pub fn configure(max_level: Option<Level>, targets: Option<Vec<String>>) {
+    *TARGETS.write().unwrap_or_else(|e| e.into_inner()) = targets;
+    MAX_LEVEL.store(max_level.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+/// Reads `-v`, `-vv` and `--trace-targets T1,T2,...`.
+pub fn configure_from_args(args: &mut Args) -> Result<()> {
+    let max_level =
+        if args.flag("-vv") {
+            Some(Level::Trace)
+        }
+        else if args.flag("-v") {
+            Some(Level::Debug)
+        }
+        else {
+            None
+        };
+    let targets = args.value("--trace-targets")?.map(|targets|
+        targets.split(',').map(str::to_string).collect()
+    );
+    configure(max_level, targets);
+    Ok(())
+}
+
+/// Emitter of the messages of a day, named after its module.
+pub struct Target(pub &'static str);
+
+impl Target {
+    pub fn is_enabled(&self, level: Level) -> bool {
+        level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) &&
+            TARGETS.read().unwrap_or_else(|e| e.into_inner()).as_ref()
+                .is_none_or(|targets| targets.iter().any(|target| target == self.0))
+    }
+
+    pub fn log(&self, level: Level, message: impl FnOnce() -> String) {
+        if self.is_enabled(level) {
+            eprintln!("[{} {}] {}", level.name(), self.0, message());
+        }
+    }
+
+    pub fn debug(&self, message: impl FnOnce() -> String) {
+        self.log(Level::Debug, message)
+    }
+
+    pub fn trace(&self, message: impl FnOnce() -> String) {
+        self.log(Level::Trace, message)
+    }
+}
@@ -0,0 +1,172 @@
+use aoc_core::{
+    Result, args::Args, explain, memory, parallel,
+    solver::{Context, Solution, Solver}
+};
+
+mod scaffold;
+
+const USAGE: &str = "\
+Usage: aoc run <day> [--year Y] [--jobs N] [--print-lines]
+               [--explain[=table|json]] [-v|-vv] [--trace-targets DAY,...]
+               [--alloc-stats]
+       aoc run --all [--year Y] [--inputs DIR] [--jobs N] [-v|-vv]
+               [--trace-targets DAY,...]
+       aoc gen <day> [--year Y] [--seed N] [--size S]
+       aoc check [<day>] [--year Y] [--seed N] [--cases C]
+       aoc new <year> <day>";
+
+/// Event years with puzzles, the last one being the default.
+const YEARS: &[u32] = &[2023];
+
+fn year(args: &mut Args) -> Result<u32> {
+    Ok(args.parse("--year")?.unwrap_or(YEARS[YEARS.len() - 1]))
+}
+
+fn solvers(year: u32) -> Result<&'static [&'static dyn Solver]> {
+    match year {
+        2023 => Ok(aoc2023::days::SOLVERS),
+        _ => Err(format!("No puzzles for year {year}"))?,
+    }
+}
+
+fn solver(year: u32, day: u32) -> Result<&'static dyn Solver> {
+    let solvers = solvers(year)?;
+    day.checked_sub(1).and_then(|index| solvers.get(index as usize)).copied()
+        .ok_or(format!("No solver for day {day} of {year}").into())
+}
+
+fn run_day(year: u32, day: u32, inputs: &str) -> Result<Solution> {
+    let path = format!("{inputs}/ac{day:02}.txt");
+    let file = std::fs::File::open(&path).map_err(|e| format!("{path}: {e}"))?;
+    /* Days already run in parallel with each other, so each day runs
+       sequentially. */
+    solver(year, day)?
+        .solve(&Context::default(), Box::new(std::io::BufReader::new(file)))
+}
+
+fn run(mut args: Args) -> Result<()> {
+    let context = Context::from_args(&mut args)?;
+    let year = year(&mut args)?;
+    if args.flag("--all") {
+        let inputs = args.value("--inputs")?.unwrap_or("inputs".to_string());
+        /* The days run in parallel, so their allocations cannot be told
+           apart. */
+        if args.flag("--alloc-stats") {
+            Err("--alloc-stats only applies to a single day")?
+        }
+        args.finish()?;
+        let all_days: Vec<u32> = (1 ..= solvers(year)?.len() as u32).collect();
+        let solutions = parallel::map(
+            context.jobs, &all_days, |&day| run_day(year, day, &inputs)
+        );
+        let mut failed = 0;
+        for (day, solution) in all_days.iter().zip(solutions) {
+            println!("Day {day}");
+            match solution {
+                Ok(solution) => print!("{solution}"),
+                Err(error) => {
+                    println!("Error: {error:?}");
+                    failed += 1;
+                }
+            }
+        }
+        if failed > 0 {
+            Err(format!("{failed} days failed"))?
+        }
+    }
+    else {
+        let alloc_stats = args.flag("--alloc-stats");
+        if alloc_stats && !memory::ENABLED {
+            Err("--alloc-stats needs the alloc-stats feature")?
+        }
+        let day = args.positional().ok_or(USAGE)?.parse()?;
+        args.finish()?;
+        let input = Box::new(std::io::stdin().lock());
+        memory::phase("solve");
+        print!("{}", solver(year, day)?.solve(&context, input)?);
+        if alloc_stats {
+            let rows: Vec<explain::Row> = memory::finish().iter().map(|phase|
+                explain::Row::new()
+                    .with("name", phase.name)
+                    .with("allocations", phase.allocations)
+                    .with("bytes", phase.bytes)
+                    .with("peak live bytes", phase.peak_live_bytes)
+            ).collect();
+            explain::print(
+                &mut std::io::stdout().lock(), explain::Format::Table, "phase", &rows
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn gen(mut args: Args) -> Result<()> {
+    let year = year(&mut args)?;
+    let seed = args.parse("--seed")?.unwrap_or(0);
+    let size = args.parse("--size")?.unwrap_or(10);
+    let day = args.positional().ok_or(USAGE)?.parse()?;
+    args.finish()?;
+    let input =
+        match year {
+            2023 => aoc2023::generators::generate(day, seed, size)?,
+            _ => Err(format!("No generators for year {year}, only for 2023"))?,
+        };
+    print!("{input}");
+    Ok(())
+}
+
+fn check(mut args: Args) -> Result<()> {
+    let year = year(&mut args)?;
+    let seed = args.parse("--seed")?.unwrap_or(0);
+    let cases = args.parse("--cases")?.unwrap_or(100);
+    if year != 2023 {
+        Err(format!("No differential checks for year {year}, only for 2023"))?
+    }
+    let days: Vec<u32> =
+        match args.positional() {
+            None => aoc2023::differential::days().collect(),
+            Some(day) => vec![day.parse()?],
+        };
+    args.finish()?;
+    let mut failed = 0;
+    for day in days {
+        let report = aoc2023::differential::check(day, seed, cases)?;
+        println!(
+            "Day {day}: {} passed, {} skipped, {} failed",
+            report.passed, report.skipped, report.failures.len()
+        );
+        for failure in &report.failures {
+            println!("Seed {}, size {}, shrunk to:", failure.seed, failure.size);
+            print!("{}", failure.input);
+            println!("Fast:      {}", failure.fast);
+            println!("Reference: {}", failure.reference);
+        }
+        failed += report.failures.len();
+    }
+    if failed > 0 {
+        Err(format!("{failed} failures"))?
+    }
+    Ok(())
+}
+
+fn new(mut args: Args) -> Result<()> {
+    let year = args.positional().ok_or(USAGE)?.parse()?;
+    let day = args.positional().ok_or(USAGE)?.parse()?;
+    args.finish()?;
+    for path in scaffold::new_day(&scaffold::workspace_root()?, year, day)? {
+        println!("Created {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    match args.positional().as_deref() {
+        Some("run") => run(args),
+        Some("gen") => gen(args),
+        Some("check") => check(args),
+        Some("new") => new(args),
+        Some(command) => Err(format!("Unknown command {command}\n{USAGE}"))?,
+        None => Err(USAGE)?,
+    }
+}
@@ -0,0 +1,343 @@
+//! `aoc new <year> <day>`: creates a day from templates and registers it.
+//!
+//! For day `acNN` of year `Y`, the files created in the workspace are:
+//! - `aocY/src/days/acNN.rs`, the solver, added to `aocY/src/days/mod.rs`;
+//! - `aocY/src/bin/acNN.rs`, the binary solving standard input;
+//! - `aocY/tests/acNN.rs`, a test on the example, failing until the
+//!   example and its expected answer are filled in;
+//! - `aocY/tests/data/acNN/example.txt`, empty.
+//!
+//! The crate of a new year is created as well, and added to the workspace
+//! and to the years of the runner. `aoc run` then solves its days, but
+//! `aoc gen` and `aoc check` reject it: a new year has neither input
+//! generators nor reference solvers.
+
+use std::path::{Path, PathBuf};
+
+use aoc_core::Result;
+
+const DAY_TEMPLATE: &str = "\
+use std::io::BufRead;
+
+use aoc_core::{Result, solver::{Context, Input, Solution}};
+
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let lines: Vec<String> = input.lines().collect::<std::io::Result<_>>()?;
+    let result_part1 = lines.len();
+    Ok(Solution::part1(result_part1))
+}
+";
+
+const BIN_TEMPLATE: &str = "\
+use aoc_core::{Result, solver};
+use aocYEAR::days::MODULE;
+
+fn main() -> Result<()> {
+    solver::main(&MODULE::solve)
+}
+";
+
+const TEST_TEMPLATE: &str = "\
+use aoc_core::solver::Context;
+use aocYEAR::days::MODULE;
+
+#[test]
+fn example() {
+    let input = include_str!(\"data/MODULE/example.txt\");
+    let solution =
+        MODULE::solve(&Context::default(), Box::new(input.as_bytes())).unwrap();
+    assert_eq!(solution.part1, \"expected part 1\");
+}
+";
+
+const MANIFEST_TEMPLATE: &str = "\
+[package]
+name = \"aocYEAR\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+aoc-core = { path = \"../aoc-core\" }
+";
+
+const LIB_TEMPLATE: &str = "\
+pub mod days;
+";
+
+const DAYS_TEMPLATE: &str = "\
+use aoc_core::solver::Solver;
+
+pub const SOLVERS: &[&dyn Solver] = &[
+];
+";
+
+fn instantiate(template: &str, year: u32, module: &str) -> String {
+    template.replace("YEAR", &year.to_string()).replace("MODULE", module)
+}
+
+/// Returns the root of the workspace, the first directory from the current
+/// one upwards whose manifest declares a workspace.
+pub fn workspace_root() -> Result<PathBuf> {
+    let current = std::env::current_dir()?;
+    current.ancestors().find(|dir|
+        std::fs::read_to_string(dir.join("Cargo.toml"))
+            .is_ok_and(|manifest| manifest.contains("[workspace]"))
+    ).map(Path::to_path_buf).ok_or("Not in the workspace".into())
+}
+
+fn read(path: &Path) -> Result<String> {
+    Ok(std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    Ok(std::fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+/// Inserts `line` after the last line satisfying `after`, or before the
+/// first line satisfying `before` if there is none.
+fn insert_line(
+    text: &str, line: &str, after: impl Fn(&str) -> bool,
+    before: impl Fn(&str) -> bool
+) -> Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let index =
+        match lines.iter().rposition(|line| after(line)) {
+            Some(index) => index + 1,
+            None => lines.iter().position(|line| before(line))
+                .ok_or(format!("Cannot insert {line}"))?,
+        };
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Returns the files edited to add the crate of a new year to the workspace
+/// and to the runner.
+fn register_year(root: &Path, year: u32) -> Result<Vec<(PathBuf, String)>> {
+    let crate_name = format!("aoc{year}");
+    let workspace_path = root.join("Cargo.toml");
+    let workspace = read(&workspace_path)?;
+    let members = workspace.replacen(
+        ", \"aoc\"]", &format!(", \"{crate_name}\", \"aoc\"]"), 1
+    );
+    if members == workspace {
+        Err("Cannot find the runner in the workspace members")?
+    }
+    let runner_manifest_path = root.join("aoc/Cargo.toml");
+    let mut runner_manifest = read(&runner_manifest_path)?;
+    runner_manifest.push_str(
+        &format!("{crate_name} = {{ path = \"../{crate_name}\" }}\n")
+    );
+    let runner_path = root.join("aoc/src/main.rs");
+    let runner = read(&runner_path)?;
+    let years_line = runner.lines().find(|line| line.starts_with("const YEARS"))
+        .ok_or("Cannot find the years of the runner")?;
+    let (_, years) = years_line.rsplit_once("&[").ok_or("Invalid years")?;
+    let mut years: Vec<u32> =
+        years.trim_end_matches("];").split(", ").map(str::parse)
+            .collect::<std::result::Result<_, _>>()?;
+    years.push(year);
+    years.sort();
+    let years: Vec<String> = years.iter().map(u32::to_string).collect();
+    let runner = runner.replacen(
+        years_line, &format!("const YEARS: &[u32] = &[{}];", years.join(", ")), 1
+    );
+    let is_solvers_arm = |line: &str|
+        line.trim_start().split_once(" => Ok(aoc")
+            .is_some_and(|(_, rest)| rest.ends_with("::days::SOLVERS),"));
+    let runner = insert_line(
+        &runner, &format!("        {year} => Ok({crate_name}::days::SOLVERS),"),
+        is_solvers_arm, |_| false
+    )?;
+    Ok(vec![
+        (workspace_path, members),
+        (runner_manifest_path, runner_manifest),
+        (runner_path, runner),
+    ])
+}
+
+/// Creates day `day` of `year` in the workspace at `root` and returns the
+/// paths of the files created. Nothing is written unless all the edits can
+/// be made.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    if !(1 ..= 25).contains(&day) {
+        Err(format!("Invalid day {day}"))?
+    }
+    let module = format!("ac{day:02}");
+    let crate_dir = root.join(format!("aoc{year}"));
+    let days_path = crate_dir.join("src/days/mod.rs");
+    let files = [
+        (crate_dir.join(format!("src/days/{module}.rs")), DAY_TEMPLATE),
+        (crate_dir.join(format!("src/bin/{module}.rs")), BIN_TEMPLATE),
+        (crate_dir.join(format!("tests/{module}.rs")), TEST_TEMPLATE),
+        (crate_dir.join(format!("tests/data/{module}/example.txt")), ""),
+    ];
+    for (path, _) in &files {
+        if path.exists() {
+            Err(format!("{} already exists", path.display()))?
+        }
+    }
+    let new_year = !crate_dir.exists();
+    let days =
+        if new_year {
+            DAYS_TEMPLATE.to_string()
+        }
+        else {
+            read(&days_path)?
+        };
+    if days.contains(&format!("pub mod {module};")) {
+        Err(format!("Day {day} of {year} already exists"))?
+    }
+    let solver_count = days.lines().filter(|line| line.ends_with("::solve,")).count();
+    if solver_count + 1 != day as usize {
+        Err(format!(
+            "Days are added in order: the next day of {year} is {}",
+            solver_count + 1
+        ))?
+    }
+    let mut created: Vec<(PathBuf, String)> = Vec::new();
+    let mut edited = Vec::new();
+    if new_year {
+        for (path, template) in [
+            (crate_dir.join("Cargo.toml"), MANIFEST_TEMPLATE),
+            (crate_dir.join("src/lib.rs"), LIB_TEMPLATE),
+        ] {
+            created.push((path, instantiate(template, year, &module)));
+        }
+        edited = register_year(root, year)?;
+    }
+    let days = insert_line(
+        &days, &format!("pub mod {module};"),
+        |line| line.starts_with("pub mod "), |line| line.starts_with("pub const SOLVERS")
+    )?;
+    let days =
+        if days.contains("pub mod ac01;\npub const") {
+            days.replacen("pub mod ac01;\n", "pub mod ac01;\n\n", 1)
+        }
+        else {
+            days
+        };
+    let days = insert_line(
+        &days, &format!("    &{module}::solve,"),
+        |line| line.ends_with("::solve,"),
+        |line| line == "];"
+    )?;
+    if new_year {
+        created.push((days_path, days));
+    }
+    else {
+        edited.push((days_path, days));
+    }
+    for (path, template) in files {
+        created.push((path, instantiate(template, year, &module)));
+    }
+    for (path, contents) in created.iter().chain(&edited) {
+        write(path, contents)?;
+    }
+    Ok(created.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Copy of the workspace manifest and of the runner in a new temporary
+    /// directory.
+    fn workspace(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write(&root.join("Cargo.toml"), include_str!("../../Cargo.toml")).unwrap();
+        write(&root.join("aoc/Cargo.toml"), include_str!("../Cargo.toml")).unwrap();
+        write(&root.join("aoc/src/main.rs"), include_str!("main.rs")).unwrap();
+        root
+    }
+
+    fn error<T>(result: Result<T>) -> String {
+        match result {
+            Ok(_) => panic!("Expected an error"),
+            Err(aoc_core::Error(message)) => message,
+        }
+    }
+
+    #[test]
+    fn new_year() {
+        let root = workspace("new-year");
+        let created = new_day(&root, 2099, 1).unwrap();
+        let crate_dir = root.join("aoc2099");
+        assert_eq!(created, [
+            "Cargo.toml", "src/lib.rs", "src/days/mod.rs", "src/days/ac01.rs",
+            "src/bin/ac01.rs", "tests/ac01.rs", "tests/data/ac01/example.txt",
+        ].map(|path| crate_dir.join(path)));
+        assert_eq!(read(&crate_dir.join("src/days/mod.rs")).unwrap(), concat!(
+            "use aoc_core::solver::Solver;\n\n",
+            "pub mod ac01;\n\n",
+            "pub const SOLVERS: &[&dyn Solver] = &[\n",
+            "    &ac01::solve,\n",
+            "];\n",
+        ));
+        assert!(read(&crate_dir.join("src/bin/ac01.rs")).unwrap()
+                .contains("use aoc2099::days::ac01;"));
+        assert!(read(&crate_dir.join("tests/ac01.rs")).unwrap()
+                .contains("include_str!(\"data/ac01/example.txt\")"));
+        assert!(read(&root.join("Cargo.toml")).unwrap()
+                .contains("members = [\"aoc-core\", \"aoc2023\", \"aoc2099\", \"aoc\"]"));
+        assert!(read(&root.join("aoc/Cargo.toml")).unwrap()
+                .ends_with("aoc2099 = { path = \"../aoc2099\" }\n"));
+        let runner = read(&root.join("aoc/src/main.rs")).unwrap();
+        assert!(runner.contains("const YEARS: &[u32] = &[2023, 2099];"));
+        assert!(runner.contains(concat!(
+            "        2023 => Ok(aoc2023::days::SOLVERS),\n",
+            "        2099 => Ok(aoc2099::days::SOLVERS),\n",
+        )));
+
+        new_day(&root, 2099, 2).unwrap();
+        assert!(read(&crate_dir.join("src/days/mod.rs")).unwrap().contains(concat!(
+            "pub mod ac01;\npub mod ac02;\n\n",
+            "pub const SOLVERS: &[&dyn Solver] = &[\n",
+            "    &ac01::solve,\n",
+            "    &ac02::solve,\n",
+            "];\n",
+        )));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn existing_days_are_kept() {
+        let root = workspace("existing-days");
+        new_day(&root, 2099, 1).unwrap();
+        let day_path = root.join("aoc2099/src/days/ac01.rs");
+        write(&day_path, "// Solved\n").unwrap();
+        assert_eq!(
+            error(new_day(&root, 2099, 1)),
+            format!("{} already exists", day_path.display())
+        );
+        assert_eq!(read(&day_path).unwrap(), "// Solved\n");
+        /* A file left over from another day: nothing is written. */
+        let days = read(&root.join("aoc2099/src/days/mod.rs")).unwrap();
+        let test_path = root.join("aoc2099/tests/ac02.rs");
+        write(&test_path, "").unwrap();
+        assert_eq!(
+            error(new_day(&root, 2099, 2)),
+            format!("{} already exists", test_path.display())
+        );
+        assert_eq!(read(&root.join("aoc2099/src/days/mod.rs")).unwrap(), days);
+        assert!(!root.join("aoc2099/src/days/ac02.rs").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn days_in_order() {
+        let root = workspace("days-in-order");
+        assert_eq!(
+            error(new_day(&root, 2099, 3)),
+            "Days are added in order: the next day of 2099 is 1"
+        );
+        assert_eq!(error(new_day(&root, 2099, 26)), "Invalid day 26");
+        assert!(!root.join("aoc2099").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
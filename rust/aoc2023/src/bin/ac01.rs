@@ -0,0 +1,15 @@
+use aoc_core::{Result, args::Args, solver::Context};
+use aoc2023::days::ac01;
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let vocabulary = ac01::Vocabulary::from_args(&mut args)?;
+    let policy = ac01::Policy::from_args(&mut args)?;
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    let solution = ac01::solve_with(
+        &context, Box::new(std::io::stdin().lock()), vocabulary, policy
+    )?;
+    print!("{solution}");
+    Ok(())
+}
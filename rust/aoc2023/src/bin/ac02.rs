@@ -0,0 +1,44 @@
+use std::io::BufRead;
+
+use aoc_core::{Result, args::Args, explain, solver::Context};
+use aoc2023::days::ac02;
+
+fn read_games() -> Result<Vec<ac02::Game>> {
+    std::io::stdin().lock().lines().map(|line| ac02::parse_game(&line?)).collect()
+}
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let bag = args.value("--bag")?.unwrap_or(ac02::DEFAULT_BAG.to_string()).parse()?;
+    let likelihood = args.flag("--likelihood");
+    let estimate_bag = args.flag("--estimate-bag");
+    let max_cubes = args.parse("--max-cubes")?.unwrap_or(100);
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    if likelihood {
+        let rows: Vec<explain::Row> =
+            ac02::rank_games(&bag, &read_games()?).iter().map(|(id, log)|
+                explain::Row::new()
+                    .with("game", *id)
+                    .with("log likelihood", format!("{log:.4}"))
+                    .with("probability", format!("{:.4e}", log.exp()))
+            ).collect();
+        explain::print(
+            &mut std::io::stdout().lock(), context.explain.unwrap_or(explain::Format::Table),
+            "rank", &rows
+        )?;
+    }
+    else if estimate_bag {
+        let (bag, log) = ac02::estimate_bag(&read_games()?, max_cubes)?;
+        println!("Most likely bag: {bag} (log likelihood {log:.4})");
+        if ac02::total(&bag) == max_cubes {
+            println!("The bag has --max-cubes cubes: a larger bag may be more likely");
+        }
+    }
+    else {
+        let solution =
+            ac02::solve_with_bag(&context, Box::new(std::io::stdin().lock()), bag)?;
+        print!("{solution}");
+    }
+    Ok(())
+}
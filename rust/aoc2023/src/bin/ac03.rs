@@ -0,0 +1,13 @@
+use aoc_core::{Result, args::Args, solver::Context};
+use aoc2023::days::ac03;
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let rule = ac03::GearRule::from_args(&mut args)?;
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    let solution =
+        ac03::solve_with_rule(&context, Box::new(std::io::stdin().lock()), &rule)?;
+    print!("{solution}");
+    Ok(())
+}
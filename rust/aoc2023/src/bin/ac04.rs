@@ -0,0 +1,37 @@
+use std::io::BufRead;
+
+use aoc_core::{Result, args::Args, explain, solver::Context};
+use aoc2023::days::ac04;
+
+fn read_cards() -> Result<Vec<usize>> {
+    std::io::stdin().lock().lines()
+        .map(|line| ac04::winning_numbers_count(&line?)).collect()
+}
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let ledger =
+        if args.flag("--ledger") {
+            Some(explain::Format::Table)
+        }
+        else {
+            args.parse("--ledger")?
+        };
+    let validate = args.flag("--validate");
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    if ledger.is_none() && !validate {
+        print!("{}", ac04::solve(&context, Box::new(std::io::stdin().lock()))?);
+        return Ok(());
+    }
+    let cards = read_cards()?;
+    if let Some(format) = ledger {
+        let rows = ac04::ledger_rows(&ac04::ledger(&cards));
+        explain::print(&mut std::io::stdout().lock(), format, "card", &rows)?;
+    }
+    if validate {
+        let result = ac04::validate(&cards)?;
+        println!("Stack and ledger agree on the {} cards: Part 2 is {result}", cards.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use aoc_core::{Result, args::Args, solver::Context};
+use aoc2023::days::ac05::{self, IntervalSet};
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let print_map = args.flag("--print-map");
+    let from = args.value("--from")?;
+    let to = args.value("--to")?;
+    let reverse: Option<IntervalSet> = args.parse("--reverse")?;
+    let lowest: Option<u64> = args.parse("--lowest")?;
+    let _context = Context::from_args(&mut args)?;
+    args.finish()?;
+    let almanac = ac05::Almanac::parse(Box::new(std::io::stdin().lock()))?;
+    let from = from.as_deref().unwrap_or(ac05::SEED);
+    let to = match &to {
+        Some(to) => to,
+        None => almanac.last_category()?,
+    };
+    let map = almanac.compose(from, to)?;
+    if print_map {
+        print!("{map}");
+        return Ok(());
+    }
+    let seeds = (from == ac05::SEED).then(|| almanac.seed_ranges()).transpose()?;
+    let locations =
+        match (reverse, lowest) {
+            (None, None) => {
+                if seeds.is_none() {
+                    Err(format!(
+                        "The seeds are not {from} values: use --print-map or --reverse"
+                    ))?
+                }
+                print!("{}", ac05::solve_with_map(&almanac, &map)?);
+                return Ok(());
+            }
+            (Some(_), Some(_)) => Err("--reverse and --lowest are exclusive")?,
+            (Some(locations), None) => locations,
+            (None, Some(count)) => {
+                let seeds = seeds.as_ref().ok_or("--lowest applies to the seed ranges")?;
+                map.image(seeds).lowest(count)
+            }
+        };
+    let preimage = map.preimage(&locations);
+    print!("Preimage:\n{preimage}");
+    if let Some(seeds) = seeds {
+        print!("In the seed ranges:\n{}", preimage.intersection(&seeds));
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use aoc_core::{Result, args::Args, solver::Context};
+use aoc2023::days::ac06;
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let physics = ac06::Physics::from_args(&mut args)?;
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    let solution =
+        ac06::solve_with_physics(&context, Box::new(std::io::stdin().lock()), &physics)?;
+    print!("{solution}");
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use aoc_core::{Result, args::Args, explain, solver::Context};
+use aoc2023::days::ac07;
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let day =
+        match ac07::Rules::from_args(&mut args)? {
+            None => ac07::Day::default(),
+            Some(rules) => ac07::Day { rules: vec![rules] },
+        };
+    let report =
+        if args.flag("--report") {
+            Some(explain::Format::Table)
+        }
+        else {
+            args.parse("--report")?
+        };
+    let part: Option<usize> = args.parse("--part")?;
+    let compare = args.value("--compare")?;
+    let context = Context::from_args(&mut args)?;
+    /* The second hand is positional, so it is read once all the options
+       are taken. */
+    let compare =
+        match compare {
+            None => None,
+            Some(hand1) => {
+                let hand2 = args.positional().ok_or("--compare takes two hands")?;
+                Some((hand1, hand2))
+            }
+        };
+    args.finish()?;
+    let parts: Vec<(usize, &ac07::Rules)> =
+        match part {
+            None => (1 ..).zip(&day.rules).collect(),
+            Some(part) => {
+                let rules = part.checked_sub(1).and_then(|index| day.rules.get(index))
+                    .ok_or(format!("No part {part}"))?;
+                vec![(part, rules)]
+            }
+        };
+    if let Some((hand1, hand2)) = compare {
+        for (part, rules) in parts {
+            println!("Part {part}: {}", ac07::Comparison::new(rules, &hand1, &hand2)?);
+        }
+    }
+    else if let Some(format) = report {
+        let hands = ac07::read_hands(&day, Box::new(std::io::stdin().lock()))?;
+        for (part, rules) in parts {
+            if let explain::Format::Table = format {
+                println!("Part {part}");
+            }
+            let rows = ac07::ranking(rules, &hands);
+            explain::print(&mut std::io::stdout().lock(), format, "rank", &rows)?;
+        }
+    }
+    else {
+        let solution =
+            ac07::solve_with_rules(&context, Box::new(std::io::stdin().lock()), &day)?;
+        print!("{solution}");
+    }
+    Ok(())
+}
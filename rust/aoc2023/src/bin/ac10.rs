@@ -0,0 +1,6 @@
+use aoc_core::{Result, solver};
+use aoc2023::days::ac10;
+
+fn main() -> Result<()> {
+    solver::main(&ac10::solve)
+}
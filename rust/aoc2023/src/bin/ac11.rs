@@ -0,0 +1,6 @@
+use aoc_core::{Result, solver};
+use aoc2023::days::ac11;
+
+fn main() -> Result<()> {
+    solver::main(&ac11::solve)
+}
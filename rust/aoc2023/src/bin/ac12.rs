@@ -0,0 +1,6 @@
+use aoc_core::{Result, solver};
+use aoc2023::days::ac12;
+
+fn main() -> Result<()> {
+    solver::main(&ac12::solve)
+}
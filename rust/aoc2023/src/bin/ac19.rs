@@ -0,0 +1,6 @@
+use aoc_core::{Result, solver};
+use aoc2023::days::ac19;
+
+fn main() -> Result<()> {
+    solver::main(&ac19::solve)
+}
@@ -0,0 +1,14 @@
+use aoc_core::{Result, args::Args, animation::Recorder, solver::Context};
+use aoc2023::days::ac22;
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let mut recorder = Recorder::from_args(&mut args)?;
+    let context = Context::from_args(&mut args)?;
+    args.finish()?;
+    let solution =
+        ac22::solve_recording(&context, Box::new(std::io::stdin().lock()), &mut recorder)?;
+    recorder.finish()?;
+    print!("{solution}");
+    Ok(())
+}
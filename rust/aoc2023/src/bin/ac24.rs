@@ -0,0 +1,6 @@
+use aoc_core::{Result, solver};
+use aoc2023::days::ac24;
+
+fn main() -> Result<()> {
+    solver::main(&ac24::solve)
+}
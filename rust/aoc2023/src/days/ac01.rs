@@ -0,0 +1,512 @@
+use aoc_core::{
+    Error, Result, args::Args, explain::{Row, Value},
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+const DIGIT_NUMBERS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const ENGLISH_NUMBERS: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const FRENCH_NUMBERS: [(&str, u32); 10] = [
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+const ENGLISH_ORDINALS: [(&str, u32); 10] = [
+    ("zeroth", 0),
+    ("first", 1),
+    ("second", 2),
+    ("third", 3),
+    ("fourth", 4),
+    ("fifth", 5),
+    ("sixth", 6),
+    ("seventh", 7),
+    ("eighth", 8),
+    ("ninth", 9),
+];
+
+/// Tokens standing for digits in Part 2, with their values.
+#[derive(Clone, Default)]
+pub struct Vocabulary(Vec<(String, u32)>);
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, token: &str, value: u32) -> Result<()> {
+        if token.is_empty() {
+            Err("Empty token")?
+        }
+        if value > 9 {
+            Err(format!("Token {token} should stand for a digit, not {value}"))?
+        }
+        self.0.push((token.to_string(), value));
+        Ok(())
+    }
+
+    fn extend(&mut self, numbers: &[(&str, u32)]) {
+        self.0.extend(numbers.iter().map(|&(token, value)| (token.to_string(), value)));
+    }
+
+    /// Adds one of the built-in vocabularies: `english`, `french` or
+    /// `ordinals`.
+    pub fn add_builtin(&mut self, name: &str) -> Result<()> {
+        match name {
+            "english" => self.extend(&ENGLISH_NUMBERS),
+            "french" => self.extend(&FRENCH_NUMBERS),
+            "ordinals" => self.extend(&ENGLISH_ORDINALS),
+            _ => Err(format!("Unknown vocabulary {name}"))?,
+        }
+        Ok(())
+    }
+
+    /// Adds the tokens of a vocabulary file, which has a token and its
+    /// value on each non-empty line.
+    pub fn add_file(&mut self, contents: &str) -> Result<()> {
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let (token, value) =
+                line.trim().rsplit_once(char::is_whitespace)
+                .ok_or(format!("Expected a token and a value: {line}"))?;
+            self.add(token.trim_end(), value.parse()?)?;
+        }
+        Ok(())
+    }
+
+    /// Reads `--words NAME,...` (`english` by default) and
+    /// `--words-file PATH`. Digits are always tokens.
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        let mut vocabulary = Self::new();
+        vocabulary.extend(&DIGIT_NUMBERS);
+        let names = args.value("--words")?;
+        let path = args.value("--words-file")?;
+        if names.is_none() && path.is_none() {
+            vocabulary.add_builtin("english")?;
+        }
+        for name in names.iter().flat_map(|names| names.split(',')) {
+            vocabulary.add_builtin(name)?;
+        }
+        if let Some(path) = path {
+            vocabulary.add_file(
+                &std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?
+            )?;
+        }
+        Ok(vocabulary)
+    }
+}
+
+/// Occurrence of a token of the vocabulary.
+#[derive(Clone, Copy)]
+pub struct Match {
+    pub start: usize,
+    pub token: usize,
+}
+
+/// Aho–Corasick automaton recognizing the tokens of a vocabulary in a single
+/// pass, overlapping occurrences included. It works on bytes, which is
+/// enough to find UTF-8 tokens in UTF-8 texts.
+pub struct Matcher {
+    vocabulary: Vocabulary,
+    /// Transitions of each state, failure links already followed.
+    transitions: Vec<[usize; 256]>,
+    /// Token ending in each state, if any.
+    tokens: Vec<Option<usize>>,
+    /// Closest state along the failure links where a token ends.
+    output_links: Vec<Option<usize>>,
+}
+
+impl Matcher {
+    pub fn new(vocabulary: Vocabulary) -> Self {
+        let mut children: Vec<std::collections::HashMap<u8, usize>> =
+            vec![std::collections::HashMap::new()];
+        let mut tokens = vec![None];
+        for (index, (token, _value)) in vocabulary.0.iter().enumerate() {
+            let mut state = 0;
+            for &byte in token.as_bytes() {
+                state =
+                    match children[state].get(&byte) {
+                        Some(&child) => child,
+                        None => {
+                            children.push(std::collections::HashMap::new());
+                            tokens.push(None);
+                            let child = children.len() - 1;
+                            children[state].insert(byte, child);
+                            child
+                        }
+                    };
+            }
+            tokens[state].get_or_insert(index);
+        }
+        let mut transitions = vec![[0; 256]; children.len()];
+        let mut failure_links = vec![0; children.len()];
+        let mut output_links = vec![None; children.len()];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0 ..= 255 {
+                let failure = transitions[failure_links[state]][byte as usize];
+                match children[state].get(&byte) {
+                    Some(&child) => {
+                        transitions[state][byte as usize] = child;
+                        let link = if state == 0 { 0 } else { failure };
+                        failure_links[child] = link;
+                        output_links[child] =
+                            if tokens[link].is_some() {
+                                Some(link)
+                            }
+                            else {
+                                output_links[link]
+                            };
+                        queue.push_back(child);
+                    }
+                    None => transitions[state][byte as usize] = failure,
+                }
+            }
+        }
+        Self { vocabulary, transitions, tokens, output_links }
+    }
+
+    pub fn token(&self, token: usize) -> &str {
+        &self.vocabulary.0[token].0
+    }
+
+    pub fn value(&self, token: usize) -> u32 {
+        self.vocabulary.0[token].1
+    }
+
+    pub fn matches<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Match> + 'a {
+        text.bytes().enumerate().scan(0, |state, (index, byte)| {
+            *state = self.transitions[*state][byte as usize];
+            Some((index + 1, *state))
+        }).flat_map(move |(end, state)|
+            std::iter::successors(
+                Some(state).filter(|&state| self.tokens[state].is_some())
+                    .or(self.output_links[state]),
+                |&state| self.output_links[state]
+            ).filter_map(move |state| {
+                let token = self.tokens[state]?;
+                Some(Match { start: end - self.token(token).len(), token })
+            })
+        )
+    }
+}
+
+/// What to do with a line without digit (Part 1) or without digit nor
+/// number word (Part 2).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Skip,
+    Zero,
+    Fail,
+}
+
+impl std::str::FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skip" => Ok(Policy::Skip),
+            "zero" => Ok(Policy::Zero),
+            "fail" => Ok(Policy::Fail),
+            _ => Err(format!("Unknown policy {s}, expected skip, zero or fail"))?,
+        }
+    }
+}
+
+impl Policy {
+    /// Reads `--missing skip|zero|fail`, `fail` by default.
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        Ok(args.parse("--missing")?.unwrap_or(Policy::Fail))
+    }
+}
+
+/// Calibration value of a line for each part, `None` if the line has no
+/// digit for this part.
+pub struct Calibration {
+    part1: Option<u32>,
+    part2: Option<u32>,
+    digits: Option<(char, char)>,
+    tokens: Option<(usize, usize)>,
+}
+
+fn format_part(part: Option<u32>) -> String {
+    part.map_or("-".to_string(), |value| value.to_string())
+}
+
+impl std::fmt::Display for Calibration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", format_part(self.part1), format_part(self.part2))
+    }
+}
+
+#[derive(Default)]
+pub struct Totals {
+    sums: [u64; 2],
+    missing: [usize; 2],
+}
+
+pub struct Day {
+    matcher: Matcher,
+    policy: Policy,
+}
+
+fn digit_value(c: char) -> u32 {
+    c as u32 - '0' as u32
+}
+
+impl Day {
+    pub fn new(vocabulary: Vocabulary, policy: Policy) -> Self {
+        Self { matcher: Matcher::new(vocabulary), policy }
+    }
+
+    /// Returns the first and the last tokens of `line`. Among tokens starting
+    /// at the same position, the longest wins.
+    fn first_and_last_tokens(&self, line: &str) -> Option<(Match, Match)> {
+        let length = |m: &Match| self.matcher.token(m.token).len();
+        let mut matches = self.matcher.matches(line);
+        let first_match = matches.next()?;
+        Some(matches.fold((first_match, first_match), |(left, right), m| {
+            let left =
+                if (m.start, std::cmp::Reverse(length(&m))) <
+                    (left.start, std::cmp::Reverse(length(&left))) {
+                    m
+                }
+                else {
+                    left
+                };
+            let right =
+                if (m.start, length(&m)) > (right.start, length(&right)) {
+                    m
+                }
+                else {
+                    right
+                };
+            (left, right)
+        }))
+    }
+
+    fn explain_part(&self, part: Option<u32>) -> Value {
+        match (part, self.policy) {
+            (Some(value), _) => value.into(),
+            (None, Policy::Zero) => 0u32.into(),
+            (None, _) => "skipped".into(),
+        }
+    }
+
+    fn format_total(&self, sum: u64, missing: usize) -> String {
+        let lines = if missing == 1 { "line" } else { "lines" };
+        match self.policy {
+            _ if missing == 0 => sum.to_string(),
+            Policy::Zero => format!("{sum} ({missing} {lines} counted as 0)"),
+            _ => format!("{sum} ({missing} {lines} skipped)"),
+        }
+    }
+}
+
+impl LineSolver for Day {
+    type Line = Calibration;
+    type State = Totals;
+
+    fn line(&self, line: &str) -> Result<Calibration> {
+        let digits =
+            line.chars().find(char::is_ascii_digit)
+            .zip(line.chars().rev().find(char::is_ascii_digit));
+        let tokens = self.first_and_last_tokens(line);
+        if self.policy == Policy::Fail {
+            if digits.is_none() {
+                Err("No digit")?
+            }
+            if tokens.is_none() {
+                Err("No digit nor number word")?
+            }
+        }
+        Ok(Calibration {
+            part1: digits.map(|(first, last)|
+                digit_value(first) * 10 + digit_value(last)
+            ),
+            part2: tokens.map(|(left, right)|
+                self.matcher.value(left.token) * 10 + self.matcher.value(right.token)
+            ),
+            digits,
+            tokens: tokens.map(|(left, right)| (left.token, right.token)),
+        })
+    }
+
+    fn fold(&self, state: &mut Totals, line: Calibration) -> Result<()> {
+        for (part, value) in [line.part1, line.part2].into_iter().enumerate() {
+            match value {
+                Some(value) =>
+                    state.sums[part] = state.sums[part].checked_add(value as u64)
+                        .ok_or(format!("Sum of part {} overflows", part + 1))?,
+                None => state.missing[part] += 1,
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&self, state: Totals) -> Result<Solution> {
+        Ok(Solution::new(
+            self.format_total(state.sums[0], state.missing[0]),
+            self.format_total(state.sums[1], state.missing[1])
+        ))
+    }
+
+    fn explain(&self, _state: &Totals, line: &Calibration) -> Row {
+        let mut row = Row::new();
+        if let Some((first, last)) = line.digits {
+            row.set("first digit", first);
+            row.set("last digit", last);
+        }
+        row.set("part 1", self.explain_part(line.part1));
+        if let Some((first, last)) = line.tokens {
+            row.set("first token", self.matcher.token(first));
+            row.set("last token", self.matcher.token(last));
+        }
+        row.set("part 2", self.explain_part(line.part2));
+        row
+    }
+}
+
+pub fn solve_with(
+    context: &Context, input: Input, vocabulary: Vocabulary, policy: Policy
+) -> Result<Solution> {
+    stream(&Day::new(vocabulary, policy), context, input)
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    let mut vocabulary = Vocabulary::new();
+    vocabulary.extend(&DIGIT_NUMBERS);
+    vocabulary.add_builtin("english")?;
+    solve_with(context, input, vocabulary, Policy::Fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> Vocabulary {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.extend(&DIGIT_NUMBERS);
+        vocabulary.add_builtin("english").unwrap();
+        vocabulary
+    }
+
+    fn sorted_matches<'a>(matcher: &'a Matcher, text: &str) -> Vec<(usize, &'a str)> {
+        let mut matches: Vec<(usize, &str)> =
+            matcher.matches(text).map(|m| (m.start, matcher.token(m.token))).collect();
+        matches.sort();
+        matches
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let matcher = Matcher::new(english());
+        assert_eq!(sorted_matches(&matcher, "eightwo"), [(0, "eight"), (4, "two")]);
+        assert_eq!(sorted_matches(&matcher, "oneight"), [(0, "one"), (2, "eight")]);
+        assert_eq!(
+            sorted_matches(&matcher, "4nineeightseven2"),
+            [(0, "4"), (1, "nine"), (5, "eight"), (10, "seven"), (15, "2")]
+        );
+    }
+
+    #[test]
+    fn calibration_values() {
+        let day = Day::new(english(), Policy::Skip);
+        for (line, part1, part2) in [
+            ("eightwo", None, 82),
+            ("oneight", None, 18),
+            ("xtwone3four", Some(33), 24),
+            ("7pqrstsixteen", Some(77), 76),
+            ("zoneight234", Some(24), 14),
+            ("5twone", Some(55), 51),
+        ] {
+            let calibration = day.line(line).unwrap();
+            assert_eq!((calibration.part1, calibration.part2), (part1, Some(part2)), "{line}");
+        }
+    }
+
+    /* Tokens sharing prefixes and suffixes, where the failure links matter. */
+    #[test]
+    fn matches_every_occurrence() {
+        let mut vocabulary = Vocabulary::new();
+        for (token, value) in [("he", 1), ("she", 2), ("his", 3), ("hers", 4), ("e", 5)] {
+            vocabulary.add(token, value).unwrap();
+        }
+        let matcher = Matcher::new(vocabulary.clone());
+        for text in ["ushers", "shehishers", "hhershe", "", "xyz", "eeee"] {
+            let mut expected: Vec<(usize, &str)> = vocabulary.0.iter()
+                .flat_map(|(token, _)|
+                    (0 .. text.len()).filter(|&start| text[start ..].starts_with(token.as_str()))
+                        .map(|start| (start, token.as_str()))
+                ).collect();
+            expected.sort();
+            assert_eq!(sorted_matches(&matcher, text), expected, "{text}");
+        }
+    }
+
+    /* Three lines without digit, one of them without number word either. */
+    const MISSING_DIGITS: &str = "1abc2\nnothing\ntwo\n7xy\nnone at all\n";
+
+    fn solve_policy(policy: Policy) -> Result<Solution> {
+        solve_with(&Context::default(), Box::new(MISSING_DIGITS.as_bytes()), english(), policy)
+    }
+
+    #[test]
+    fn skip_policy_counts_skipped_lines() {
+        let solution = solve_policy(Policy::Skip).unwrap();
+        assert_eq!(solution.part1, "89 (3 lines skipped)");
+        assert_eq!(solution.part2.unwrap(), "122 (1 line skipped)");
+    }
+
+    #[test]
+    fn zero_policy_counts_zero_lines() {
+        let solution = solve_policy(Policy::Zero).unwrap();
+        assert_eq!(solution.part1, "89 (3 lines counted as 0)");
+        assert_eq!(solution.part2.unwrap(), "122 (1 line counted as 0)");
+    }
+
+    #[test]
+    fn fail_policy_reports_the_line() {
+        let Err(Error(message)) = solve_policy(Policy::Fail) else {
+            panic!("Lines without digit should fail")
+        };
+        assert_eq!(message, "Line 2: No digit");
+        let mut french = Vocabulary::new();
+        french.add_builtin("french").unwrap();
+        let Err(Error(message)) = solve_with(
+            &Context::default(), Box::new("4deux\n7\n".as_bytes()), french, Policy::Fail
+        ) else {
+            panic!("Lines without number word should fail")
+        };
+        assert_eq!(message, "Line 2: No digit nor number word");
+    }
+}
@@ -0,0 +1,321 @@
+use aoc_core::{
+    Error, Result, explain::Row,
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+/// Count of cubes of each colour, in the order the colours first appear.
+#[derive(Clone, Default)]
+pub struct CubeSet(Vec<(String, u64)>);
+
+impl CubeSet {
+    pub fn get(&self, color: &str) -> u64 {
+        self.0.iter().find(|(c, _)| c == color).map_or(0, |&(_, count)| count)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(color, _)| color.as_str())
+    }
+
+    fn raise(&mut self, color: &str, count: u64) {
+        match self.0.iter_mut().find(|(c, _)| c == color) {
+            Some((_, old)) => *old = (*old).max(count),
+            None => self.0.push((color.to_string(), count)),
+        }
+    }
+}
+
+impl std::fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (color, count)) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{color}={count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Bag contents, written `red=12,green=13,blue=14`.
+impl std::str::FromStr for CubeSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut set = CubeSet::default();
+        for item in s.split(',') {
+            let (color, count) =
+                item.split_once('=').ok_or(format!("Expected color=count: {item}"))?;
+            if set.colors().any(|c| c == color) {
+                Err(format!("Color {color} given twice"))?
+            }
+            set.raise(color, count.parse()?);
+        }
+        Ok(set)
+    }
+}
+
+pub struct Game {
+    id: u64,
+    sets: Vec<CubeSet>,
+}
+
+pub fn parse_game(line: &str) -> Result<Game> {
+    let (game_str, set_str) = line.split_once(": ").ok_or("No ': ' found")?;
+    let id: u64 =
+        game_str.strip_prefix("Game ").ok_or("No 'Game ' found")?.parse()?;
+    let sets = set_str.split("; ").map(|set_str| {
+        let mut set = CubeSet::default();
+        for color_cube_str in set_str.split(", ") {
+            let (count_str, color) =
+                color_cube_str.split_once(" ")
+                .ok_or(format!("Expected count and color: {color_cube_str}"))?;
+            set.raise(color, count_str.parse()?);
+        }
+        Ok(set)
+    }).collect::<Result<_>>()?;
+    Ok(Game { id, sets })
+}
+
+/// Draw exceeding the bag contents for a colour.
+pub struct BrokenLimit {
+    draw: usize,
+    color: String,
+    count: u64,
+}
+
+pub struct GameResult {
+    id: u64,
+    minimum_bag: CubeSet,
+    broken_limits: Vec<BrokenLimit>,
+    power: u64,
+}
+
+impl GameResult {
+    fn valid(&self) -> bool {
+        self.broken_limits.is_empty()
+    }
+}
+
+impl std::fmt::Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.id, self.valid(), self.power)
+    }
+}
+
+pub struct Day {
+    bag: CubeSet,
+}
+
+impl Day {
+    pub fn new(bag: CubeSet) -> Self {
+        Self { bag }
+    }
+}
+
+impl LineSolver for Day {
+    type Line = GameResult;
+    type State = (u64, u64);
+
+    fn line(&self, line: &str) -> Result<GameResult> {
+        let game = parse_game(line)?;
+        let mut broken_limits = Vec::new();
+        /* The colours of the bag count in the power even when the game draws
+           none of them. */
+        let mut minimum_bag = CubeSet::default();
+        for color in self.bag.colors() {
+            minimum_bag.raise(color, 0);
+        }
+        for (draw, set) in game.sets.iter().enumerate() {
+            for (color, count) in &set.0 {
+                if *count > self.bag.get(color) {
+                    broken_limits.push(BrokenLimit {
+                        draw: draw + 1, color: color.clone(), count: *count
+                    });
+                }
+                minimum_bag.raise(color, *count);
+            }
+        }
+        let power = minimum_bag.0.iter().map(|(_, count)| count).product();
+        Ok(GameResult { id: game.id, minimum_bag, broken_limits, power })
+    }
+
+    fn fold(&self, state: &mut (u64, u64), line: GameResult) -> Result<()> {
+        if line.valid() {
+            state.0 += line.id;
+        }
+        state.1 += line.power;
+        Ok(())
+    }
+
+    fn finish(&self, (result_part1, result_part2): (u64, u64)) -> Result<Solution> {
+        Ok(Solution::new(result_part1, result_part2))
+    }
+
+    fn explain(&self, _state: &(u64, u64), game: &GameResult) -> Row {
+        let broken_limits: Vec<String> = game.broken_limits.iter().map(|broken|
+            format!("draw {}: {} {}", broken.draw, broken.count, broken.color)
+        ).collect();
+        Row::new()
+            .with("game", game.id)
+            .with("minimum bag", game.minimum_bag.to_string())
+            .with("possible", game.valid())
+            .with("broken limits", broken_limits.join(", "))
+            .with("part 1", if game.valid() { game.id } else { 0 })
+            .with("part 2", game.power)
+    }
+}
+
+/// Logarithms of the factorials up to some bound.
+struct LnFactorials(Vec<f64>);
+
+impl LnFactorials {
+    fn new(max: u64) -> Self {
+        let mut table = vec![0.];
+        for n in 1 ..= max {
+            table.push(table[table.len() - 1] + (n as f64).ln());
+        }
+        Self(table)
+    }
+
+    fn ln_binomial(&self, n: u64, k: u64) -> f64 {
+        if k > n {
+            f64::NEG_INFINITY
+        }
+        else {
+            self.0[n as usize] - self.0[k as usize] - self.0[(n - k) as usize]
+        }
+    }
+}
+
+pub fn total(set: &CubeSet) -> u64 {
+    set.0.iter().map(|(_, count)| count).sum()
+}
+
+/* Cubes are put back in the bag after each draw, so draws are independent,
+   and each of them follows a multivariate hypergeometric distribution: the
+   probability of drawing k_c cubes of each colour c from a bag with K_c
+   cubes of each colour is prod_c C(K_c, k_c) / C(sum_c K_c, sum_c k_c). */
+
+fn draw_log_likelihood(table: &LnFactorials, bag: &CubeSet, draw: &CubeSet) -> f64 {
+    /* Both terms would be minus infinity, and their difference NaN. */
+    if total(draw) > total(bag)
+        || draw.0.iter().any(|(color, count)| *count > bag.get(color))
+    {
+        return f64::NEG_INFINITY;
+    }
+    draw.0.iter().map(|(color, count)| table.ln_binomial(bag.get(color), *count))
+        .sum::<f64>() - table.ln_binomial(total(bag), total(draw))
+}
+
+/// Returns the natural logarithm of the probability of the draws of `game`
+/// for `bag`, minus infinity if they are impossible.
+fn game_log_likelihood(table: &LnFactorials, bag: &CubeSet, game: &Game) -> f64 {
+    game.sets.iter().map(|draw| draw_log_likelihood(table, bag, draw)).sum()
+}
+
+/// Returns the identifiers of the games with their log-likelihoods for
+/// `bag`, most likely first.
+pub fn rank_games(bag: &CubeSet, games: &[Game]) -> Vec<(u64, f64)> {
+    let table = LnFactorials::new(total(bag));
+    let mut ranking: Vec<(u64, f64)> = games.iter().map(|game|
+        (game.id, game_log_likelihood(&table, bag, game))
+    ).collect();
+    ranking.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    ranking
+}
+
+/// Returns the bag of at most `max_cubes` cubes for which all the draws are
+/// the most likely, with their log-likelihood.
+///
+/// The log-likelihood of a bag is `sum_c f_c(K_c) - g(sum_c K_c)`, where
+/// `f_c(K)` sums `ln C(K, k_c)` over the draws and `g(N)` sums
+/// `ln C(N, sum_c k_c)`. For each total `N`, the best split of `N` between
+/// the colours is found by dynamic programming over the colours.
+pub fn estimate_bag(games: &[Game], max_cubes: u64) -> Result<(CubeSet, f64)> {
+    let draws: Vec<&CubeSet> = games.iter().flat_map(|game| &game.sets).collect();
+    let mut minimum_bag = CubeSet::default();
+    for draw in &draws {
+        for (color, count) in &draw.0 {
+            minimum_bag.raise(color, *count);
+        }
+    }
+    if total(&minimum_bag) > max_cubes {
+        Err(format!(
+            "The draws need at least {} cubes, more than {max_cubes}",
+            total(&minimum_bag)
+        ))?
+    }
+    let table = LnFactorials::new(max_cubes);
+    let max = max_cubes as usize;
+    /* best[c][n]: best sum of f over the first c colours with n cubes, and
+       the count of colour c - 1 achieving it. */
+    let mut best = vec![vec![(f64::NEG_INFINITY, 0); max + 1]];
+    best[0][0] = (0., 0);
+    for (color, minimum) in &minimum_bag.0 {
+        let f: Vec<f64> = (0 ..= max_cubes).map(|count| draws.iter().map(|draw|
+            table.ln_binomial(count, draw.get(color))
+        ).sum()).collect();
+        let previous = &best[best.len() - 1];
+        let mut next = vec![(f64::NEG_INFINITY, 0); max + 1];
+        for (n, cell) in next.iter_mut().enumerate() {
+            for count in *minimum as usize ..= n {
+                let value = previous[n - count].0 + f[count];
+                if value > cell.0 {
+                    *cell = (value, count);
+                }
+            }
+        }
+        best.push(next);
+    }
+    let last = &best[best.len() - 1];
+    /* Smaller bags cannot hold every draw. */
+    let (log_likelihood, mut n) = (total(&minimum_bag) as usize ..= max).map(|n|
+        (last[n].0 - draws.iter().map(|draw|
+            table.ln_binomial(n as u64, total(draw))
+        ).sum::<f64>(), n)
+    ).max_by(|(a, _), (b, _)| a.total_cmp(b)).ok_or("No bag")?;
+    let mut counts = Vec::new();
+    for column in best[1 ..].iter().rev() {
+        let count = column[n].1;
+        counts.push(count as u64);
+        n -= count;
+    }
+    counts.reverse();
+    let bag = CubeSet(
+        minimum_bag.colors().map(str::to_string).zip(counts).collect()
+    );
+    Ok((bag, log_likelihood))
+}
+
+pub const DEFAULT_BAG: &str = "red=12,green=13,blue=14";
+
+pub fn solve_with_bag(context: &Context, input: Input, bag: CubeSet) -> Result<Solution> {
+    stream(&Day::new(bag), context, input)
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    solve_with_bag(context, input, DEFAULT_BAG.parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impossible_draw_is_minus_infinity() {
+        let game = parse_game("Game 1: 2 red, 2 green").unwrap();
+        let bag: CubeSet = "red=1,green=1,blue=1".parse().unwrap();
+        assert_eq!(rank_games(&bag, &[game]), vec![(1, f64::NEG_INFINITY)]);
+    }
+
+    #[test]
+    fn estimated_bag_holds_every_draw() {
+        let games = [
+            parse_game("Game 1: 3 red, 1 green").unwrap(),
+            parse_game("Game 2: 1 blue").unwrap(),
+        ];
+        let (bag, log_likelihood) = estimate_bag(&games, 20).unwrap();
+        assert!(log_likelihood.is_finite());
+        assert!(bag.get("red") >= 3 && bag.get("green") >= 1 && bag.get("blue") >= 1);
+    }
+}
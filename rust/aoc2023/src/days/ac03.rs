@@ -0,0 +1,314 @@
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, Coords2D, Matrix2D, args::Args, explain::{self, Row},
+    matrix_from_lines, solver::{Context, Input, Solution}
+};
+
+/// Number written on line `y`, from column `start` to column `end - 1`.
+pub struct Number {
+    pub value: u64,
+    pub y: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+pub struct Symbol {
+    pub c: char,
+    pub position: Coords2D<usize>,
+}
+
+/// Numbers and symbols of an engine schematic, with the bipartite graph of
+/// their adjacency (diagonals included).
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// Indices of the symbols adjacent to each number.
+    number_neighbors: Vec<Vec<usize>>,
+    /// Indices of the numbers adjacent to each symbol.
+    symbol_neighbors: Vec<Vec<usize>>,
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+impl Schematic {
+    pub fn new(grid: &Matrix2D<char>) -> Result<Self> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut symbol_indices = std::collections::HashMap::new();
+        for (y, line) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                let c = line[x];
+                if c.is_ascii_digit() {
+                    let start = x;
+                    while x < line.len() && line[x].is_ascii_digit() {
+                        x += 1;
+                    }
+                    let number_str: String = line[start .. x].iter().collect();
+                    numbers.push(Number { value: number_str.parse()?, y, start, end: x });
+                    continue;
+                }
+                if is_symbol(c) {
+                    let position = Coords2D { x, y };
+                    symbol_indices.insert(position, symbols.len());
+                    symbols.push(Symbol { c, position });
+                }
+                x += 1;
+            }
+        }
+        let mut symbol_neighbors = vec![Vec::new(); symbols.len()];
+        let number_neighbors = numbers.iter().enumerate().map(|(index, number)| {
+            let mut neighbors = Vec::new();
+            for y in number.y.saturating_sub(1) ..= number.y + 1 {
+                for x in number.start.saturating_sub(1) ..= number.end {
+                    if let Some(&symbol) = symbol_indices.get(&Coords2D { x, y }) {
+                        neighbors.push(symbol);
+                        symbol_neighbors[symbol].push(index);
+                    }
+                }
+            }
+            neighbors
+        }).collect();
+        Ok(Self { numbers, symbols, number_neighbors, symbol_neighbors })
+    }
+
+    /// Numbers adjacent to at least one symbol satisfying `symbol`.
+    pub fn numbers_next_to(
+        &self, symbol: impl Fn(char) -> bool
+    ) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().zip(&self.number_neighbors)
+            .filter(move |(_number, neighbors)|
+                neighbors.iter().any(|&index| symbol(self.symbols[index].c))
+            )
+            .map(|(number, _neighbors)| number)
+    }
+
+    /// Symbols satisfying `symbol` adjacent to exactly `count` numbers, with
+    /// these numbers.
+    pub fn gears(
+        &self, symbol: impl Fn(char) -> bool, count: usize
+    ) -> impl Iterator<Item = (&Symbol, Vec<&Number>)> {
+        self.symbols.iter().zip(&self.symbol_neighbors)
+            .filter(move |(s, neighbors)| symbol(s.c) && neighbors.len() == count)
+            .map(|(symbol, neighbors)|
+                (symbol, neighbors.iter().map(|&index| &self.numbers[index]).collect())
+            )
+    }
+
+    /// Numbers adjacent to no symbol.
+    pub fn isolated_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().zip(&self.number_neighbors)
+            .filter(|(_number, neighbors)| neighbors.is_empty())
+            .map(|(number, _neighbors)| number)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Combine {
+    Product,
+    Sum,
+    Max,
+}
+
+impl std::str::FromStr for Combine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "product" => Ok(Combine::Product),
+            "sum" => Ok(Combine::Sum),
+            "max" => Ok(Combine::Max),
+            _ => Err(format!("Unknown operation {s}, expected product, sum or max"))?,
+        }
+    }
+}
+
+impl Combine {
+    fn apply(self, values: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Combine::Product => values.product(),
+            Combine::Sum => values.sum(),
+            Combine::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+/// Part 2: a gear is one of `symbols` adjacent to exactly `neighbors`
+/// numbers, and its ratio combines these numbers.
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub neighbors: usize,
+    pub combine: Combine,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self { symbols: vec!['*'], neighbors: 2, combine: Combine::Product }
+    }
+}
+
+impl GearRule {
+    /// Reads `--gear-symbols CHARS`, `--gear-neighbors N` and
+    /// `--gear-combine product|sum|max`.
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        let default = Self::default();
+        let symbols =
+            match args.value("--gear-symbols")? {
+                None => default.symbols,
+                Some(symbols) => {
+                    let symbols: Vec<char> = symbols.chars().collect();
+                    if let Some(c) = symbols.iter().find(|&&c| !is_symbol(c)) {
+                        Err(format!("{c} is not a symbol"))?
+                    }
+                    symbols
+                }
+            };
+        let neighbors = args.parse("--gear-neighbors")?.unwrap_or(default.neighbors);
+        let combine = args.parse("--gear-combine")?.unwrap_or(default.combine);
+        Ok(Self { symbols, neighbors, combine })
+    }
+}
+
+pub fn solve_with_rule(
+    context: &Context, input: Input, rule: &GearRule
+) -> Result<Solution> {
+    let schematic = Schematic::new(&matrix_from_lines(input.lines())?)?;
+    let result_part1: u64 =
+        schematic.numbers_next_to(|_| true).map(|number| number.value).sum();
+    let gears: Vec<(&Symbol, u64, Vec<&Number>)> =
+        schematic.gears(|c| rule.symbols.contains(&c), rule.neighbors)
+        .map(|(gear, numbers)| {
+            let ratio = rule.combine.apply(numbers.iter().map(|number| number.value));
+            (gear, ratio, numbers)
+        }).collect();
+    if let Some(format) = context.explain {
+        let rows: Vec<Row> = gears.iter().map(|(gear, ratio, numbers)| {
+            let values: Vec<u64> = numbers.iter().map(|number| number.value).collect();
+            Row::new()
+                .with("symbol", gear.c)
+                .with("x", gear.position.x)
+                .with("y", gear.position.y)
+                .with("numbers", values)
+                .with("ratio", *ratio)
+        }).collect();
+        explain::print(&mut std::io::stdout().lock(), format, "gear", &rows)?;
+    }
+    let result_part2: u64 = gears.iter().map(|(_gear, ratio, _numbers)| ratio).sum();
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    solve_with_rule(context, input, &GearRule::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    fn example() -> Schematic {
+        Schematic::new(&matrix_from_lines(EXAMPLE.as_bytes().lines()).unwrap()).unwrap()
+    }
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u64> {
+        numbers.map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn numbers_and_symbols() {
+        let schematic = example();
+        assert_eq!(
+            values(schematic.numbers.iter()),
+            [467, 114, 35, 633, 617, 58, 592, 755, 664, 598]
+        );
+        let number = &schematic.numbers[3];
+        assert_eq!((number.y, number.start, number.end), (2, 6, 9));
+        let symbols: Vec<(char, usize, usize)> = schematic.symbols.iter()
+            .map(|symbol| (symbol.c, symbol.position.x, symbol.position.y)).collect();
+        assert_eq!(
+            symbols,
+            [('*', 3, 1), ('#', 6, 3), ('*', 3, 4), ('+', 5, 5), ('$', 3, 8), ('*', 5, 8)]
+        );
+    }
+
+    #[test]
+    fn adjacency() {
+        let schematic = example();
+        assert_eq!(values(schematic.isolated_numbers()), [114, 58]);
+        assert_eq!(values(schematic.numbers_next_to(|c| c == '*')), [467, 35, 617, 755, 598]);
+        assert_eq!(values(schematic.numbers_next_to(|c| c == '$' || c == '#')), [633, 664]);
+        let gears: Vec<(usize, usize, Vec<u64>)> = schematic.gears(|c| c == '*', 2)
+            .map(|(gear, numbers)|
+                (gear.position.x, gear.position.y, values(numbers.into_iter()))
+            ).collect();
+        assert_eq!(gears, [(3, 1, vec![467, 35]), (5, 8, vec![755, 598])]);
+    }
+
+    /* Numbers touching a symbol by a corner only, or at the edges of the
+       grid. */
+    #[test]
+    fn diagonals_and_edges() {
+        let grid = matrix_from_lines("1...2\n.@.#.\n3...4\n".as_bytes().lines()).unwrap();
+        let schematic = Schematic::new(&grid).unwrap();
+        assert_eq!(values(schematic.numbers_next_to(|c| c == '@')), [1, 3]);
+        assert_eq!(values(schematic.numbers_next_to(|c| c == '#')), [2, 4]);
+        assert!(schematic.isolated_numbers().next().is_none());
+    }
+
+    fn solve_with_args(args: &[&str]) -> Result<Solution> {
+        let mut args = Args::new(args.iter().map(|arg| arg.to_string()));
+        let rule = GearRule::from_args(&mut args)?;
+        args.finish()?;
+        solve_with_rule(&Context::default(), Box::new(EXAMPLE.as_bytes()), &rule)
+    }
+
+    fn part2(args: &[&str]) -> String {
+        solve_with_args(args).unwrap().part2.unwrap()
+    }
+
+    #[test]
+    fn default_gears() {
+        let solution = solve_with_args(&[]).unwrap();
+        assert_eq!(solution.part1, "4361");
+        assert_eq!(solution.part2.unwrap(), "467835");
+    }
+
+    #[test]
+    fn gear_symbols() {
+        assert_eq!(part2(&["--gear-symbols", "$"]), "0");
+        assert_eq!(part2(&["--gear-symbols", "#$+", "--gear-neighbors", "1"]), "1889");
+        let Err(Error(message)) = solve_with_args(&["--gear-symbols", "*5"]) else {
+            panic!("Digits are not symbols")
+        };
+        assert_eq!(message, "5 is not a symbol");
+    }
+
+    #[test]
+    fn gear_neighbors() {
+        assert_eq!(part2(&["--gear-neighbors", "1"]), "617");
+        assert_eq!(part2(&["--gear-neighbors=3"]), "0");
+    }
+
+    #[test]
+    fn gear_combine() {
+        assert_eq!(part2(&["--gear-combine", "sum"]), "1855");
+        assert_eq!(part2(&["--gear-combine", "max"]), "1222");
+        assert_eq!(part2(&["--gear-combine", "product"]), "467835");
+        assert!(solve_with_args(&["--gear-combine", "min"]).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use aoc_core::{
+    Result, explain::Row,
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+/// Set of card numbers, which are below 128.
+#[derive(Clone, Copy, Default)]
+struct NumberSet(u128);
+
+impl NumberSet {
+    /// Parses the numbers separated by spaces of `s`, which should be
+    /// distinct.
+    fn parse(s: &str) -> Result<Self> {
+        let mut set = NumberSet::default();
+        for token in s.split_whitespace() {
+            let number: u32 =
+                token.parse().map_err(|_| format!("Invalid number '{token}'"))?;
+            let bit = 1u128.checked_shl(number)
+                .ok_or(format!("Number {number} should be below 128"))?;
+            if set.0 & bit != 0 {
+                Err(format!("Duplicate number {number}"))?
+            }
+            set.0 |= bit;
+        }
+        Ok(set)
+    }
+
+    fn intersection_count(self, other: Self) -> usize {
+        (self.0 & other.0).count_ones() as usize
+    }
+}
+
+pub fn winning_numbers_count(line: &str) -> Result<usize> {
+    let (card_str, contents) = line.split_once(':').ok_or("Missing ':'")?;
+    let id_str = card_str.strip_prefix("Card").ok_or("Missing 'Card'")?;
+    id_str.trim().parse::<u64>().map_err(|_| format!("Invalid card number '{id_str}'"))?;
+    let (winning_numbers_str, numbers_str) =
+        contents.split_once(" | ").ok_or("Missing ' | '")?;
+    let winning_numbers = NumberSet::parse(winning_numbers_str)?;
+    let numbers = NumberSet::parse(numbers_str)?;
+    Ok(winning_numbers.intersection_count(numbers))
+}
+
+pub fn part1(cards: &[usize]) -> usize {
+    cards.iter().map(
+	|&count| if count == 0 { 0 } else { 1 << (count - 1) }
+    ).sum()
+}
+
+/// Copies of the next cards won so far, the next card on top.
+#[derive(Default)]
+pub struct Copies(Vec<usize>);
+
+impl Copies {
+    /// Number of copies of the next card, once the previous cards are
+    /// scratched.
+    pub fn peek(&self) -> usize {
+	self.0.last().cloned().unwrap_or(1)
+    }
+
+    /// Number of copies of the next card, which has `count` matches.
+    pub fn next(&mut self, count: usize) -> usize {
+	let copies_count = self.0.pop().unwrap_or(1);
+	/* The next `count` cards get the copies of this one: these already
+	   won are on top of the stack, the others start with one copy and go
+	   below them. */
+	let won = count.min(self.0.len());
+	let len = self.0.len();
+	for copies in &mut self.0[len - won ..] {
+	    *copies += copies_count;
+	}
+	self.0.splice(0 .. 0, std::iter::repeat_n(1 + copies_count, count - won));
+	copies_count
+    }
+}
+
+pub fn part2(cards: &[usize]) -> usize {
+    let mut copies = Copies::default();
+    cards.iter().map(|&count| copies.next(count)).sum()
+}
+
+/// Copies a card ends with, and the earlier cards they were won from.
+pub struct LedgerEntry {
+    pub matches: usize,
+    pub copies: usize,
+    /// Earlier cards (numbered from 1) with the copies each of them gave.
+    pub contributions: Vec<(usize, usize)>,
+}
+
+/// Derives the copies of each card with the direct recurrence: each card
+/// gives its copies to each of the next cards it wins.
+pub fn ledger(cards: &[usize]) -> Vec<LedgerEntry> {
+    let mut entries: Vec<LedgerEntry> = cards.iter().map(|&matches|
+        LedgerEntry { matches, copies: 1, contributions: Vec::new() }
+    ).collect();
+    for index in 0 .. entries.len() {
+        let copies = entries[index].copies;
+        let end = (index + 1 + entries[index].matches).min(entries.len());
+        for entry in &mut entries[index + 1 .. end] {
+            entry.copies += copies;
+            entry.contributions.push((index + 1, copies));
+        }
+    }
+    entries
+}
+
+pub fn ledger_rows(entries: &[LedgerEntry]) -> Vec<Row> {
+    entries.iter().map(|entry| {
+        let (cards, copies): (Vec<usize>, Vec<usize>) =
+            entry.contributions.iter().cloned().unzip();
+        Row::new()
+            .with("matches", entry.matches)
+            .with("copies", entry.copies)
+            .with("from cards", cards)
+            .with("from copies", copies)
+    }).collect()
+}
+
+/// Checks the copies of each card given by `Copies` against the ledger, and
+/// that no card wins copies past the last card.
+pub fn validate(cards: &[usize]) -> Result<usize> {
+    let mut copies = Copies::default();
+    for (number, (&count, entry)) in cards.iter().zip(ledger(cards)).enumerate() {
+        if number + 1 + count > cards.len() {
+            Err(format!(
+                "Card {}: {count} matches win copies past the last card", number + 1
+            ))?
+        }
+        let stack_copies = copies.next(count);
+        if stack_copies != entry.copies {
+            Err(format!(
+                "Card {}: {stack_copies} copies with the stack, {} with the ledger",
+                number + 1, entry.copies
+            ))?
+        }
+    }
+    Ok(part2(cards))
+}
+
+pub struct Card {
+    count: usize,
+}
+
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    points: usize,
+    cards: usize,
+    copies: Copies,
+}
+
+pub struct Day;
+
+impl LineSolver for Day {
+    type Line = Card;
+    type State = State;
+
+    fn line(&self, line: &str) -> Result<Card> {
+        Ok(Card { count: winning_numbers_count(line)? })
+    }
+
+    fn fold(&self, state: &mut State, card: Card) -> Result<()> {
+        state.points += part1(&[card.count]);
+        state.cards += state.copies.next(card.count);
+        Ok(())
+    }
+
+    fn finish(&self, state: State) -> Result<Solution> {
+        Ok(Solution::new(state.points, state.cards))
+    }
+
+    fn explain(&self, state: &State, card: &Card) -> Row {
+        Row::new()
+            .with("matches", card.count)
+            .with("part 1", part1(&[card.count]))
+            .with("copies", state.copies.peek())
+    }
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    stream(&Day, context, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::{Error, explain};
+
+    use super::*;
+
+    const EXAMPLE: [&str; 6] = [
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    ];
+
+    fn example_cards() -> Vec<usize> {
+        EXAMPLE.iter().map(|line| winning_numbers_count(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn example_matches() {
+        let cards = example_cards();
+        assert_eq!(cards, [4, 2, 2, 1, 0, 0]);
+        assert_eq!(part1(&cards), 13);
+        assert_eq!(part2(&cards), 30);
+    }
+
+    #[test]
+    fn example_ledger() {
+        let entries = ledger(&example_cards());
+        let derivation: Vec<_> = entries.iter().map(|entry|
+            (entry.matches, entry.copies, entry.contributions.clone())
+        ).collect();
+        assert_eq!(derivation, [
+            (4, 1, vec![]),
+            (2, 2, vec![(1, 1)]),
+            (2, 4, vec![(1, 1), (2, 2)]),
+            (1, 8, vec![(1, 1), (2, 2), (3, 4)]),
+            (0, 14, vec![(1, 1), (3, 4), (4, 8)]),
+            (0, 1, vec![]),
+        ]);
+        let mut out = Vec::new();
+        explain::print(&mut out, explain::Format::Json, "card", &ledger_rows(&entries))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap().lines().nth(4).unwrap(),
+            "{\"card\": 5, \"matches\": 0, \"copies\": 14, \
+             \"from cards\": [1, 3, 4], \"from copies\": [1, 4, 8]}"
+        );
+        assert_eq!(validate(&example_cards()).unwrap(), 30);
+    }
+
+    #[test]
+    fn copies_past_the_last_card() {
+        assert_eq!(validate(&[2, 1, 0]).unwrap(), 7);
+        let Err(Error(message)) = validate(&[2, 2, 0]) else {
+            panic!("Card 2 should win past the last card")
+        };
+        assert_eq!(message, "Card 2: 2 matches win copies past the last card");
+        assert!(validate(&[0, 1]).is_err());
+    }
+
+    #[test]
+    fn numbers_up_to_127() {
+        assert_eq!(NumberSet::parse("0 127").unwrap().0, 1 | 1 << 127);
+        assert_eq!(winning_numbers_count("Card 7: 0 127 5 | 127 0 6").unwrap(), 2);
+        let Err(Error(message)) = NumberSet::parse("3 128") else {
+            panic!("128 should be rejected")
+        };
+        assert_eq!(message, "Number 128 should be below 128");
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        for (numbers, expected) in [
+            ("1 x2 3", "Invalid number 'x2'"),
+            ("1 -2", "Invalid number '-2'"),
+            ("4 5 4", "Duplicate number 4"),
+        ] {
+            let Err(Error(message)) = NumberSet::parse(numbers) else {
+                panic!("{numbers} should be rejected")
+            };
+            assert_eq!(message, expected);
+        }
+    }
+
+    #[test]
+    fn malformed_cards() {
+        for (line, expected) in [
+            ("Card 1 41 48 | 83 86", "Missing ':'"),
+            ("Carte 1: 41 48 | 83 86", "Missing 'Card'"),
+            ("Card x: 41 48 | 83 86", "Invalid card number ' x'"),
+            ("Card 1: 41 48 83 86", "Missing ' | '"),
+        ] {
+            let Err(Error(message)) = winning_numbers_count(line) else {
+                panic!("{line} should be rejected")
+            };
+            assert_eq!(message, expected);
+        }
+    }
+}
@@ -0,0 +1,581 @@
+use std::io::BufRead;
+
+use aoc_core::{Error, Result, solver::{Context, Input, Solution}};
+
+/// Values from `start` to `last` are mapped to `dst` onwards.
+#[derive(Clone, Copy)]
+struct Segment {
+    start: u64,
+    last: u64,
+    dst: u64,
+}
+
+impl Segment {
+    fn map(&self, value: u64) -> u64 {
+        self.dst + (value - self.start)
+    }
+
+    fn is_identity(&self) -> bool {
+        self.start == self.dst
+    }
+}
+
+/// Set of values, as sorted disjoint intervals `(first, last)` that are not
+/// adjacent either.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct IntervalSet {
+    intervals: Vec<(u64, u64)>,
+}
+
+impl IntervalSet {
+    pub fn new(mut intervals: Vec<(u64, u64)>) -> Self {
+        intervals.retain(|(first, last)| first <= last);
+        intervals.sort();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+        for (first, last) in intervals {
+            match merged.last_mut() {
+                Some(previous) if previous.1.checked_add(1).is_none_or(|next| next >= first) =>
+                    previous.1 = previous.1.max(last),
+                _ => merged.push((first, last)),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[(u64, u64)] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.intervals.first().map(|&(first, _)| first)
+    }
+
+    /// Number of values in the set.
+    pub fn count(&self) -> u128 {
+        self.intervals.iter().map(|&(first, last)| (last - first) as u128 + 1).sum()
+    }
+
+    /// Intervals of `self` overlapping the values from `first` to `last`.
+    fn overlapping(&self, first: u64, last: u64) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let index = self.intervals.partition_point(|&(_, interval_last)| interval_last < first);
+        self.intervals[index ..].iter()
+            .take_while(move |&&(interval_first, _)| interval_first <= last)
+            .map(move |&(interval_first, interval_last)|
+                (interval_first.max(first), interval_last.min(last))
+            )
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> Self {
+        Self::new(
+            self.intervals.iter()
+                .flat_map(|&(first, last)| other.overlapping(first, last)).collect()
+        )
+    }
+
+    /// The `count` lowest values of the set.
+    pub fn lowest(&self, count: u64) -> Self {
+        let mut remaining = count;
+        let mut intervals = Vec::new();
+        for &(first, last) in &self.intervals {
+            if remaining == 0 {
+                break;
+            }
+            let last = last.min(first.saturating_add(remaining - 1));
+            intervals.push((first, last));
+            remaining -= last - first + 1;
+        }
+        Self { intervals }
+    }
+}
+
+/// Interval written `lo..hi`, `hi` excluded.
+impl std::str::FromStr for IntervalSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (lo, hi) = s.split_once("..").ok_or("'..' expected")?;
+        let (lo, hi): (u64, u64) = (lo.parse()?, hi.parse()?);
+        if hi <= lo {
+            Err(format!("Empty interval {s}"))?
+        }
+        Ok(Self::new(vec![(lo, hi - 1)]))
+    }
+}
+
+impl std::fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (first, last) in &self.intervals {
+            writeln!(f, "{first}..={last}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Function over all of `u64` made of translated intervals: the segments
+/// are sorted, contiguous and cover the whole domain, identity included.
+#[derive(Clone)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self { segments: vec![Segment { start: 0, last: u64::MAX, dst: 0 }] }
+    }
+
+    /// Builds the map of the almanac lines `(dst, src, len)`.
+    pub fn from_lines(lines: &[(u64, u64, u64)]) -> Result<Self> {
+        let mut lines: Vec<_> = lines.iter().filter(|&&(_, _, len)| len > 0).collect();
+        lines.sort_by_key(|&&(_, src, _)| src);
+        let mut segments = Vec::new();
+        let mut next = Some(0);
+        for &&(dst, src, len) in &lines {
+            if src.checked_add(len).is_none() || dst.checked_add(len).is_none() {
+                Err(format!("Range {dst} {src} {len} overflows"))?
+            }
+            let start = next.filter(|&next| next <= src)
+                .ok_or(format!("Range {dst} {src} {len} overlaps another one"))?;
+            if start < src {
+                segments.push(Segment { start, last: src - 1, dst: start });
+            }
+            segments.push(Segment { start: src, last: src + len - 1, dst });
+            next = Some(src + len);
+        }
+        if let Some(start) = next {
+            segments.push(Segment { start, last: u64::MAX, dst: start });
+        }
+        Ok(Self::normalized(segments))
+    }
+
+    /// Merges the consecutive segments translated by the same offset.
+    fn normalized(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match merged.last_mut() {
+                Some(last) if last.map(last.last).checked_add(1) == Some(segment.dst) =>
+                    last.last = segment.last,
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    fn segment_index(&self, value: u64) -> usize {
+        self.segments.partition_point(|segment| segment.start <= value) - 1
+    }
+
+    pub fn apply(&self, value: u64) -> u64 {
+        self.segments[self.segment_index(value)].map(value)
+    }
+
+    pub fn image(&self, values: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(
+            values.intervals.iter().flat_map(|&(first, last)|
+                self.segments[self.segment_index(first) ..].iter()
+                    .take_while(move |segment| segment.start <= last)
+                    .map(move |segment| (
+                        segment.map(segment.start.max(first)),
+                        segment.map(segment.last.min(last))
+                    ))
+            ).collect()
+        )
+    }
+
+    /// Values mapped into `values`. As the map is not necessarily injective,
+    /// each segment contributes its own preimage.
+    pub fn preimage(&self, values: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(
+            self.segments.iter().flat_map(|segment|
+                values.overlapping(segment.dst, segment.map(segment.last))
+                    .map(|(first, last)| (
+                        segment.start + (first - segment.dst),
+                        segment.start + (last - segment.dst)
+                    ))
+            ).collect()
+        )
+    }
+
+    /// `next` applied after `self`.
+    pub fn then(&self, next: &PiecewiseMap) -> Self {
+        let segments = self.segments.iter().flat_map(|segment| {
+            let image_last = segment.map(segment.last);
+            next.segments[next.segment_index(segment.dst) ..].iter()
+                .take_while(move |next| next.start <= image_last)
+                .map(move |next| {
+                    let image_start = next.start.max(segment.dst);
+                    let image_end = next.last.min(image_last);
+                    let start = segment.start + (image_start - segment.dst);
+                    Segment {
+                        start,
+                        last: start + (image_end - image_start),
+                        dst: next.map(image_start),
+                    }
+                })
+        }).collect();
+        Self::normalized(segments)
+    }
+}
+
+impl std::fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for segment in &self.segments {
+            if segment.is_identity() {
+                writeln!(f, "{}..={} identity", segment.start, segment.last)?;
+            }
+            else {
+                writeln!(
+                    f, "{}..={} -> {}..={}",
+                    segment.start, segment.last, segment.dst, segment.map(segment.last)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Map from category `from` to category `to`.
+pub struct Stage {
+    pub from: String,
+    pub to: String,
+    pub map: PiecewiseMap,
+}
+
+/// Category of the values of the `seeds:` line.
+pub const SEED: &str = "seed";
+
+/// Seeds and the graph of the maps between categories, which must be
+/// acyclic.
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub stages: Vec<Stage>,
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<u64>> {
+    Ok(s.split_whitespace().map(str::parse).collect::<std::result::Result<_, _>>()?)
+}
+
+impl Almanac {
+    pub fn parse(input: Input) -> Result<Self> {
+        let mut lines = input.lines();
+        let seeds = lines.next().ok_or("Empty almanac")??;
+        let seeds = parse_numbers(
+            seeds.strip_prefix("seeds: ").ok_or("Expected the seeds")?
+        )?;
+        let mut stages: Vec<Stage> = Vec::new();
+        let mut map_lines = Vec::new();
+        let mut categories: Option<(String, String)> = None;
+        for line in lines.chain([Ok(String::new())]) {
+            let line = line?;
+            if line.is_empty() {
+                if let Some((from, to)) = categories.take() {
+                    let map = PiecewiseMap::from_lines(&map_lines)?;
+                    stages.push(Stage { from, to, map });
+                    map_lines.clear();
+                }
+                continue;
+            }
+            if let Some(header) = line.strip_suffix(" map:") {
+                if categories.is_some() {
+                    Err("Expected an empty line between maps")?
+                }
+                let (from, to) = header.split_once("-to-")
+                    .ok_or(format!("Invalid map {header}"))?;
+                if stages.iter().any(|stage| stage.from == from && stage.to == to) {
+                    Err(format!("Two maps from {from} to {to}"))?
+                }
+                categories = Some((from.to_string(), to.to_string()));
+                continue;
+            }
+            if categories.is_none() {
+                Err("Range outside of a map")?
+            }
+            match parse_numbers(&line)?[..] {
+                [dst, src, len] => map_lines.push((dst, src, len)),
+                _ => Err(format!("Invalid range {line}"))?,
+            }
+        }
+        let almanac = Self { seeds, stages };
+        almanac.check_acyclic()?;
+        Ok(almanac)
+    }
+
+    fn outgoing<'a>(&'a self, category: &'a str) -> impl Iterator<Item = &'a Stage> + 'a {
+        self.stages.iter().filter(move |stage| stage.from == category)
+    }
+
+    fn categories(&self) -> std::collections::BTreeSet<&str> {
+        self.stages.iter().flat_map(|stage| [stage.from.as_str(), stage.to.as_str()])
+            .collect()
+    }
+
+    /// Removes the categories without incoming maps until none is left.
+    fn check_acyclic(&self) -> Result<()> {
+        let mut remaining = self.categories();
+        loop {
+            let sources: Vec<&str> = remaining.iter().copied().filter(|&category|
+                !self.stages.iter().any(|stage|
+                    stage.to == category && remaining.contains(stage.from.as_str())
+                )
+            ).collect();
+            if sources.is_empty() {
+                break;
+            }
+            for source in sources {
+                remaining.remove(source);
+            }
+        }
+        match remaining.first() {
+            None => Ok(()),
+            Some(category) => Err(format!("Maps form a cycle through {category}"))?,
+        }
+    }
+
+    /// The only category without maps from it.
+    pub fn last_category(&self) -> Result<&str> {
+        let sinks: Vec<&str> = self.categories().into_iter()
+            .filter(|category| self.outgoing(category).next().is_none()).collect();
+        match sinks[..] {
+            [category] => Ok(category),
+            [] => Err("No map")?,
+            _ => Err(format!("Several final categories: {}", sinks.join(", ")))?,
+        }
+    }
+
+    /// Maps leading from `from` to `to`, which must be linked by a single
+    /// chain.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&Stage>> {
+        let mut reaching = std::collections::BTreeSet::from([to]);
+        let mut pending = vec![to];
+        while let Some(category) = pending.pop() {
+            for stage in self.stages.iter().filter(|stage| stage.to == category) {
+                if reaching.insert(&stage.from) {
+                    pending.push(&stage.from);
+                }
+            }
+        }
+        if !reaching.contains(from) {
+            if self.outgoing(from).next().is_none() && from != to {
+                Err(format!("No map from {from}"))?
+            }
+            Err(format!("No chain of maps from {from} to {to}"))?
+        }
+        let mut chains = Vec::new();
+        self.find_chains(from, to, &reaching, &mut Vec::new(), &mut chains);
+        match chains.len() {
+            1 => Ok(chains.remove(0)),
+            _ => {
+                let chains: Vec<String> = chains.iter().map(|chain| {
+                    let categories: Vec<&str> =
+                        chain.iter().map(|stage| stage.to.as_str()).collect();
+                    format!("{from} -> {}", categories.join(" -> "))
+                }).collect();
+                Err(format!("Ambiguous chains: {}", chains.join(", ")))?
+            }
+        }
+    }
+
+    /// Collects up to two chains from `from` to `to`, only going through
+    /// the categories `reaching` the destination.
+    fn find_chains<'a>(
+        &'a self, from: &str, to: &str, reaching: &std::collections::BTreeSet<&str>,
+        chain: &mut Vec<&'a Stage>, chains: &mut Vec<Vec<&'a Stage>>
+    ) {
+        if from == to {
+            chains.push(chain.clone());
+            return;
+        }
+        for stage in &self.stages {
+            if chains.len() >= 2 {
+                return;
+            }
+            if stage.from == from && reaching.contains(stage.to.as_str()) {
+                chain.push(stage);
+                self.find_chains(&stage.to, to, reaching, chain, chains);
+                chain.pop();
+            }
+        }
+    }
+
+    /// Composes the maps from category `from` to category `to`.
+    pub fn compose(&self, from: &str, to: &str) -> Result<PiecewiseMap> {
+        Ok(self.chain(from, to)?.iter()
+           .fold(PiecewiseMap::identity(), |map, stage| map.then(&stage.map)))
+    }
+
+    /// Part 2 reads the seeds as pairs `start len`.
+    pub fn seed_ranges(&self) -> Result<IntervalSet> {
+        if !self.seeds.len().is_multiple_of(2) {
+            Err("Seed ranges come in pairs")?
+        }
+        let intervals = self.seeds.chunks(2)
+            .filter(|range| range[1] > 0)
+            .map(|range| Ok((
+                range[0],
+                range[0].checked_add(range[1] - 1).ok_or("Seed range overflows")?
+            )))
+            .collect::<Result<_>>()?;
+        Ok(IntervalSet::new(intervals))
+    }
+}
+
+pub fn solve_with_map(almanac: &Almanac, map: &PiecewiseMap) -> Result<Solution> {
+    let result_part1 = almanac.seeds.iter().map(|&seed| map.apply(seed)).min()
+        .ok_or("No seeds")?;
+    let result_part2 = map.image(&almanac.seed_ranges()?).min().ok_or("No seed ranges")?;
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let almanac = Almanac::parse(input)?;
+    let map = almanac.compose(SEED, almanac.last_category()?)?;
+    solve_with_map(&almanac, &map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED_TO_SOIL: [(u64, u64, u64); 2] = [(50, 98, 2), (52, 50, 48)];
+    const SOIL_TO_FERTILIZER: [(u64, u64, u64); 3] = [(0, 15, 37), (37, 52, 2), (39, 0, 15)];
+    /* Sends the lowest values to the top of the range, and back. */
+    const WRAPPING: [(u64, u64, u64); 2] = [(u64::MAX - 10, 0, 10), (0, u64::MAX - 10, 10)];
+
+    fn sample_values() -> impl Iterator<Item = u64> {
+        (0 .. 200).chain(u64::MAX - 20 ..= u64::MAX)
+    }
+
+    #[test]
+    fn then_applies_the_maps_in_turn() {
+        let maps = [&SEED_TO_SOIL[..], &SOIL_TO_FERTILIZER, &WRAPPING]
+            .map(|lines| PiecewiseMap::from_lines(lines).unwrap());
+        for first in &maps {
+            for second in &maps {
+                let composed = first.then(second);
+                for value in sample_values() {
+                    assert_eq!(composed.apply(value), second.apply(first.apply(value)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn composition_is_normalized() {
+        let map = PiecewiseMap::from_lines(&SEED_TO_SOIL).unwrap();
+        assert_eq!(
+            map.to_string(),
+            format!(
+                "0..=49 identity\n50..=97 -> 52..=99\n98..=99 -> 50..=51\n\
+                 100..={} identity\n", u64::MAX
+            )
+        );
+        let inverse = PiecewiseMap::from_lines(&[(50, 52, 48), (98, 50, 2)]).unwrap();
+        assert_eq!(map.then(&inverse).to_string(), format!("0..={} identity\n", u64::MAX));
+    }
+
+    fn contains(set: &IntervalSet, value: u64) -> bool {
+        !set.intersection(&IntervalSet::new(vec![(value, value)])).is_empty()
+    }
+
+    #[test]
+    fn preimage_holds_the_values_mapped_into_the_set() {
+        let map = PiecewiseMap::from_lines(&SEED_TO_SOIL).unwrap()
+            .then(&PiecewiseMap::from_lines(&SOIL_TO_FERTILIZER).unwrap())
+            .then(&PiecewiseMap::from_lines(&WRAPPING).unwrap());
+        for value in sample_values() {
+            let image = map.image(&IntervalSet::new(vec![(value, value)]));
+            assert!(contains(&map.preimage(&image), value), "{value}");
+        }
+        let locations: IntervalSet = "40..60".parse().unwrap();
+        let preimage = map.preimage(&locations);
+        for value in sample_values() {
+            assert_eq!(
+                contains(&preimage, value), contains(&locations, map.apply(value)), "{value}"
+            );
+        }
+    }
+
+    /* 98 and 99 are mapped to 50 and 51 by the example seed-to-soil map, and 50
+       to 52. */
+    #[test]
+    fn preimage_of_a_non_injective_map() {
+        let map = PiecewiseMap::from_lines(&SEED_TO_SOIL).unwrap();
+        let preimage = map.preimage(&IntervalSet::new(vec![(50, 52)]));
+        assert_eq!(preimage.intervals(), [(50, 50), (98, 99)]);
+        assert_eq!(preimage.lowest(2).intervals(), [(50, 50), (98, 98)]);
+    }
+
+    fn parse(almanac: &str) -> Result<Almanac> {
+        Almanac::parse(Box::new(almanac.as_bytes()))
+    }
+
+    fn error<T>(result: Result<T>) -> String {
+        match result {
+            Ok(_) => panic!("Expected an error"),
+            Err(Error(message)) => message,
+        }
+    }
+
+    /* The maps are listed out of order. */
+    const OUT_OF_ORDER: &str = "seeds: 79 14 55 13
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+seed-to-soil map:
+50 98 2
+52 50 48
+";
+
+    #[test]
+    fn maps_out_of_order() {
+        let almanac = parse(OUT_OF_ORDER).unwrap();
+        assert_eq!(almanac.last_category().unwrap(), "fertilizer");
+        let chain: Vec<&str> = almanac.chain(SEED, "fertilizer").unwrap().iter()
+            .map(|stage| stage.to.as_str()).collect();
+        assert_eq!(chain, ["soil", "fertilizer"]);
+        let map = almanac.compose(SEED, "fertilizer").unwrap();
+        assert_eq!([79, 14, 55, 13].map(|seed| map.apply(seed)), [81, 53, 57, 52]);
+    }
+
+    #[test]
+    fn missing_category() {
+        let almanac = parse(OUT_OF_ORDER).unwrap();
+        assert_eq!(error(almanac.chain(SEED, "water")), "No chain of maps from seed to water");
+        assert_eq!(error(almanac.chain("water", "soil")), "No map from water");
+        assert_eq!(error(almanac.chain("soil", SEED)), "No chain of maps from soil to seed");
+    }
+
+    #[test]
+    fn gap_between_maps() {
+        let almanac = parse("seeds: 1\n\nseed-to-soil map:\n\nwater-to-light map:\n").unwrap();
+        assert_eq!(error(almanac.last_category()), "Several final categories: light, soil");
+        assert_eq!(error(almanac.chain(SEED, "light")), "No chain of maps from seed to light");
+    }
+
+    #[test]
+    fn cycle() {
+        let almanac = "seeds: 1\n\nseed-to-soil map:\n\nsoil-to-water map:\n\n\
+                       water-to-soil map:\n";
+        assert_eq!(error(parse(almanac)), "Maps form a cycle through soil");
+    }
+
+    #[test]
+    fn ambiguous_chains() {
+        let almanac = parse(
+            "seeds: 1\n\nseed-to-soil map:\n\nsoil-to-water map:\n\nseed-to-water map:\n"
+        ).unwrap();
+        assert_eq!(
+            error(almanac.chain(SEED, "water")),
+            "Ambiguous chains: seed -> soil -> water, seed -> water"
+        );
+        assert_eq!(almanac.chain("soil", "water").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,336 @@
+use aoc_core::{
+    Error, Result, args::Args, explain::{self, Row},
+    solver::{Context, Input, Solution}
+};
+
+pub fn parse(input: &str) -> Result<(&str, &str)> {
+    let mut lines = input.lines();
+    let times_str =
+        lines.next().and_then(|line| line.strip_prefix("Time:"))
+        .ok_or("Missing 'Time:' line")?;
+    let distances_str =
+        lines.next().and_then(|line| line.strip_prefix("Distance:"))
+        .ok_or("Missing 'Distance:' line")?;
+    Ok((times_str, distances_str))
+}
+
+pub fn number_ways(time: u64, distance: u64) -> u64 {
+    /* #hold s.t. (time - hold) * hold > distance
+       i.e. - hold ** 2 + time * hold - distance > 0
+
+       In u128, time ** 2 and 4 * distance cannot overflow. The integer
+       square root gives an approximation of the smallest root, which is then
+       fixed by checking the product itself.
+     */
+    let time = time as u128;
+    let distance = distance as u128;
+    let wins = |hold: u128| (time - hold) * hold > distance;
+    let Some(delta) = (time * time).checked_sub(4 * distance) else { return 0 };
+    let mut min_holding_time = (time - delta.isqrt()) / 2;
+    while min_holding_time <= time / 2 && !wins(min_holding_time) {
+        min_holding_time += 1;
+    }
+    while min_holding_time > 0 && wins(min_holding_time - 1) {
+        min_holding_time -= 1;
+    }
+    if min_holding_time > time / 2 {
+        return 0;
+    }
+    /* Holding times are symmetric: time - min_holding_time is the largest
+       one. */
+    (time - 2 * min_holding_time + 1) as u64
+}
+
+/// Speed reached after holding the button for a given time.
+#[derive(Default)]
+pub enum Charge {
+    #[default]
+    Linear,
+    Quadratic,
+    /// Speed for each holding time, the last one holding afterwards.
+    Table(Vec<u64>),
+}
+
+impl std::str::FromStr for Charge {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "linear" => Ok(Charge::Linear),
+            "quadratic" => Ok(Charge::Quadratic),
+            _ => {
+                let speeds = s.split(',').map(str::parse).collect::<std::result::Result<_, _>>()
+                    .map_err(|_| format!(
+                        "Unknown charge {s}, expected linear, quadratic or speeds S0,S1,..."
+                    ))?;
+                Ok(Charge::Table(speeds))
+            }
+        }
+    }
+}
+
+impl Charge {
+    fn speed(&self, hold: u128) -> u128 {
+        match self {
+            Charge::Linear => hold,
+            Charge::Quadratic => hold.saturating_mul(hold),
+            Charge::Table(speeds) =>
+                speeds[(hold as usize).min(speeds.len() - 1)] as u128,
+        }
+    }
+
+    /// Splits the holding times from 0 to `time` into intervals where the
+    /// distance increases, then does not.
+    fn unimodal_intervals(&self, time: u128) -> Vec<(u128, u128)> {
+        match self {
+            Charge::Linear | Charge::Quadratic => vec![(0, time)],
+            Charge::Table(speeds) => {
+                /* With a constant speed, the distance decreases with the
+                   holding time. */
+                let mut intervals = Vec::new();
+                let mut first = 0;
+                for (hold, speeds) in speeds.windows(2).enumerate() {
+                    let hold = hold as u128 + 1;
+                    if hold > time {
+                        break;
+                    }
+                    if speeds[0] != speeds[1] {
+                        intervals.push((first, hold - 1));
+                        first = hold;
+                    }
+                }
+                intervals.push((first, time));
+                intervals
+            }
+        }
+    }
+}
+
+/// What a race is won by.
+#[derive(Clone, Copy, Default)]
+pub enum Goal {
+    /// Going further than the record of the race.
+    #[default]
+    BeatRecord,
+    /// Going at least this far.
+    Reach(u128),
+}
+
+#[derive(Default)]
+pub struct Physics {
+    pub charge: Charge,
+    pub goal: Goal,
+}
+
+impl Physics {
+    /// Reads `--charge linear|quadratic|S0,S1,...` and `--reach DISTANCE`.
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        let charge = args.parse("--charge")?.unwrap_or_default();
+        if let Charge::Table(speeds) = &charge {
+            if speeds.is_empty() {
+                Err("No speed in the charge table")?
+            }
+        }
+        let goal = args.parse("--reach")?.map_or(Goal::BeatRecord, Goal::Reach);
+        Ok(Self { charge, goal })
+    }
+
+    pub fn distance(&self, time: u64, hold: u64) -> u128 {
+        self.charge.speed(hold as u128).saturating_mul((time - hold) as u128)
+    }
+
+    /// Intervals of the holding times winning a race of `time` with this
+    /// `record`, in increasing order.
+    pub fn winning_holds(&self, time: u64, record: u64) -> Vec<(u64, u64)> {
+        let wins = |hold: u128| {
+            let distance = self.distance(time, hold as u64);
+            match self.goal {
+                Goal::BeatRecord => distance > record as u128,
+                Goal::Reach(goal) => distance >= goal,
+            }
+        };
+        let mut holds: Vec<(u64, u64)> = Vec::new();
+        for (first, last) in self.charge.unimodal_intervals(time as u128) {
+            let peak = partition_point(first, last, |hold|
+                hold < last
+                    && self.distance(time, hold as u64 + 1) > self.distance(time, hold as u64)
+            );
+            if !wins(peak) {
+                continue;
+            }
+            let start = partition_point(first, peak, |hold| !wins(hold)) as u64;
+            let end = partition_point(peak, last, wins) as u64 - 1;
+            match holds.last_mut() {
+                Some(previous) if previous.1 + 1 == start => previous.1 = end,
+                _ => holds.push((start, end)),
+            }
+        }
+        holds
+    }
+
+    /// Uses the closed form for the original race, and counts the winning
+    /// holds otherwise.
+    pub fn number_ways(&self, time: u64, record: u64) -> Result<u64> {
+        if let (Charge::Linear, Goal::BeatRecord) = (&self.charge, self.goal) {
+            return Ok(number_ways(time, record));
+        }
+        let ways: u128 = self.winning_holds(time, record).iter()
+            .map(|&(start, end)| (end - start) as u128 + 1).sum();
+        Ok(ways.try_into().map_err(|_| "Too many ways to win")?)
+    }
+}
+
+/// First value from `first` to `last` not satisfying `predicate`, which
+/// holds then does not, or `last + 1`.
+fn partition_point(first: u128, last: u128, predicate: impl Fn(u128) -> bool) -> u128 {
+    let (mut low, mut high) = (first, last + 1);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if predicate(middle) {
+            low = middle + 1;
+        }
+        else {
+            high = middle;
+        }
+    }
+    low
+}
+
+pub fn races(times_str: &str, distances_str: &str) -> Result<Vec<(u64, u64)>> {
+    let times = times_str.split_whitespace().map(str::parse);
+    let distances = distances_str.split_whitespace().map(str::parse);
+    times.zip(distances).map(|(time, distance)| Ok((time?, distance?))).collect()
+}
+
+pub fn part1(times_str: &str, distances_str: &str, physics: &Physics) -> Result<u64> {
+    races(times_str, distances_str)?.iter()
+        .map(|&(time, distance)| physics.number_ways(time, distance))
+        .product()
+}
+
+pub fn part2(times_str: &str, distances_str: &str, physics: &Physics) -> Result<u64> {
+    let time = times_str.replace(" ", "").parse()?;
+    let distance = distances_str.replace(" ", "").parse()?;
+    physics.number_ways(time, distance)
+}
+
+fn explain_row(physics: &Physics, part: u32, time: u64, record: u64) -> Result<Row> {
+    let holds: Vec<String> = physics.winning_holds(time, record).iter()
+        .map(|(start, end)| format!("{start}..={end}")).collect();
+    Ok(Row::new()
+       .with("part", part)
+       .with("time", time)
+       .with("record", record)
+       .with("winning holds", holds)
+       .with("ways", physics.number_ways(time, record)?))
+}
+
+pub fn solve_with_physics(
+    context: &Context, input: Input, physics: &Physics
+) -> Result<Solution> {
+    let input = std::io::read_to_string(input)?;
+    let (times_str, distances_str) = parse(&input)?;
+    if let Some(format) = context.explain {
+        let mut rows = races(times_str, distances_str)?.iter()
+            .map(|&(time, record)| explain_row(physics, 1, time, record))
+            .collect::<Result<Vec<_>>>()?;
+        let time = times_str.replace(" ", "").parse()?;
+        let record = distances_str.replace(" ", "").parse()?;
+        rows.push(explain_row(physics, 2, time, record)?);
+        explain::print(&mut std::io::stdout().lock(), format, "race", &rows)?;
+    }
+    let result_part1: u64 = part1(times_str, distances_str, physics)?;
+    let result_part2: u64 = part2(times_str, distances_str, physics)?;
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    solve_with_physics(context, input, &Physics::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::ac06::{number_ways as brute_force, number_ways_float};
+
+    #[test]
+    fn small_races_match_brute_force() {
+        for time in 0 .. 100 {
+            for distance in 0 ..= time * time / 4 + 2 {
+                assert_eq!(number_ways(time, distance), brute_force(time, distance));
+            }
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        /* time² == 4·distance: the best hold only ties the record. */
+        assert_eq!(number_ways(10, 25), 0);
+        assert_eq!(number_ways(10, 24), 1);
+        /* Perfect square delta: both roots are integers and excluded. */
+        assert_eq!(number_ways(10, 21), 3);
+        assert_eq!(number_ways(7, 9), 4);
+        assert_eq!(number_ways(30, 200), 9);
+        let time = 2 * 3_037_000_499;
+        assert_eq!(number_ways(time, (time / 2) * (time / 2)), 0);
+        assert_eq!(number_ways(time, (time / 2) * (time / 2) - 1), 1);
+        assert_eq!(number_ways(u64::MAX, u64::MAX), u64::MAX - 3);
+    }
+
+    #[test]
+    fn puzzle_races_match_float() {
+        /* Races of the size of the puzzle inputs, below 2^53 where the float
+           version is exact. */
+        for (time, distance) in [
+            (71530, 940200),
+            (44806572, 208158110501102),
+            (53897698, 313109012141201),
+            (61677571, 430103613071150),
+            (40828492, 233101111101487),
+        ] {
+            assert_eq!(number_ways(time, distance), number_ways_float(time, distance));
+        }
+    }
+
+    /// Maximal runs of the winning holds, checking every hold.
+    fn brute_force_holds(physics: &Physics, time: u64, record: u64) -> Vec<(u64, u64)> {
+        let mut holds: Vec<(u64, u64)> = Vec::new();
+        for hold in 0 ..= time {
+            let distance = physics.distance(time, hold);
+            let wins =
+                match physics.goal {
+                    Goal::BeatRecord => distance > record as u128,
+                    Goal::Reach(goal) => distance >= goal,
+                };
+            match holds.last_mut() {
+                _ if !wins => (),
+                Some(previous) if previous.1 + 1 == hold => previous.1 = hold,
+                _ => holds.push((hold, hold)),
+            }
+        }
+        holds
+    }
+
+    /* Tables with speeds going up and down give several separate winning
+       intervals. */
+    #[test]
+    fn winning_holds_match_brute_force() {
+        let charges = [
+            "linear", "quadratic", "3", "0,5,5,2,8,1,9", "9,1,8,2,5,5,0", "1,4,4,4,2,6,0,7,3",
+        ];
+        for charge in charges {
+            for time in 0 .. 30 {
+                for record in 0 .. 200 {
+                    for goal in [Goal::BeatRecord, Goal::Reach(record as u128)] {
+                        let physics = Physics { charge: charge.parse().unwrap(), goal };
+                        assert_eq!(
+                            physics.winning_holds(time, record),
+                            brute_force_holds(&physics, time, record),
+                            "charge {charge}, time {time}, record {record}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
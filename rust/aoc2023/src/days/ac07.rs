@@ -0,0 +1,563 @@
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, args::Args, explain::{Row, Value},
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+/// Category of hands named after their multiplicities, such as `[3, 2]` for
+/// a full house.
+pub struct Category {
+    pub name: String,
+    pub counts: Vec<usize>,
+}
+
+impl std::str::FromStr for Category {
+    type Err = Error;
+
+    /// Parses `NAME=C1,C2,...`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, counts) = s.split_once('=').ok_or("Expected NAME=C1,C2,...")?;
+        let mut counts: Vec<usize> = counts.split(',').map(str::parse)
+            .collect::<std::result::Result<_, _>>()?;
+        counts.sort_by(|a, b| b.cmp(a));
+        Ok(Self { name: name.to_string(), counts })
+    }
+}
+
+/// Rules of a Camel Cards variant. Hands are ranked by the multiplicities of
+/// their cards, sorted in decreasing order and compared lexicographically,
+/// then card by card.
+pub struct Rules {
+    pub hand_size: usize,
+    /// Cards from the strongest to the weakest.
+    pub order: Vec<char>,
+    /// Cards counting as the most frequent other card of the hand.
+    pub wildcards: Vec<char>,
+    pub categories: Vec<Category>,
+}
+
+const ORDER_PART1: &str = "AKQJT98765432";
+
+const ORDER_PART2: &str = "AKQT98765432J";
+
+const CATEGORIES: [(&str, &[usize]); 7] = [
+    ("Five", &[5]),
+    ("Four", &[4, 1]),
+    ("Full", &[3, 2]),
+    ("Three", &[3, 1, 1]),
+    ("Two", &[2, 2, 1]),
+    ("One", &[2, 1, 1, 1]),
+    ("High", &[1, 1, 1, 1, 1]),
+];
+
+impl Rules {
+    pub fn part1() -> Self {
+        Self {
+            hand_size: 5,
+            order: ORDER_PART1.chars().collect(),
+            wildcards: Vec::new(),
+            categories: CATEGORIES.iter().map(|(name, counts)|
+                Category { name: name.to_string(), counts: counts.to_vec() }
+            ).collect(),
+        }
+    }
+
+    pub fn part2() -> Self {
+        Self { order: ORDER_PART2.chars().collect(), wildcards: vec!['J'], ..Self::part1() }
+    }
+
+    /// Reads `--hand-size N`, `--order CARDS`, `--wildcards CARDS` and
+    /// `--category NAME=C1,C2,...` (repeated), if any, over the rules of
+    /// Part 1. Categories replace the default ones.
+    pub fn from_args(args: &mut Args) -> Result<Option<Self>> {
+        let hand_size = args.parse("--hand-size")?;
+        let order = args.value("--order")?;
+        let wildcards = args.value("--wildcards")?;
+        let mut categories = Vec::new();
+        while let Some(category) = args.parse("--category")? {
+            categories.push(category);
+        }
+        if hand_size.is_none() && order.is_none() && wildcards.is_none()
+            && categories.is_empty()
+        {
+            return Ok(None);
+        }
+        let default = Self::part1();
+        let rules = Self {
+            hand_size: hand_size.unwrap_or(default.hand_size),
+            order: order.map_or(default.order, |order| order.chars().collect()),
+            wildcards: wildcards.map_or(default.wildcards, |cards| cards.chars().collect()),
+            categories:
+                if categories.is_empty() && hand_size.is_none() {
+                    default.categories
+                }
+                else {
+                    categories
+                },
+        };
+        rules.check()?;
+        Ok(Some(rules))
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.hand_size == 0 {
+            Err("Hands have at least one card")?
+        }
+        for (index, card) in self.order.iter().enumerate() {
+            if self.order[.. index].contains(card) {
+                Err(format!("Card {card} is ordered twice"))?
+            }
+        }
+        if let Some(card) = self.wildcards.iter().find(|card| !self.order.contains(card)) {
+            Err(format!("Wildcard {card} is not in the order"))?
+        }
+        for category in &self.categories {
+            if category.counts.iter().sum::<usize>() != self.hand_size {
+                Err(format!(
+                    "Category {} does not have {} cards", category.name, self.hand_size
+                ))?
+            }
+        }
+        Ok(())
+    }
+
+    fn strength(&self, card: char) -> Result<usize> {
+        let index = self.order.iter().position(|&c| c == card)
+            .ok_or(format!("Invalid card {card}"))?;
+        Ok(self.order.len() - index)
+    }
+
+    /// Multiplicities of the cards of `hand` in decreasing order, the
+    /// wildcards being added to the first one.
+    pub fn counts(&self, hand: &str) -> Vec<usize> {
+        let mut counts = std::collections::HashMap::new();
+        let mut wildcards = 0;
+        for card in hand.chars() {
+            if self.wildcards.contains(&card) {
+                wildcards += 1;
+            }
+            else {
+                *counts.entry(card).or_insert(0) += 1;
+            }
+        }
+        let mut counts: Vec<usize> = counts.into_values().collect();
+        counts.sort_by(|a, b| b.cmp(a));
+        match counts.first_mut() {
+            Some(count) => *count += wildcards,
+            None => counts.push(wildcards),
+        }
+        counts
+    }
+
+    /// Name of the category of `hand`, or its multiplicities, such as
+    /// `3+2`, if no category is defined for them.
+    pub fn category(&self, hand: &str) -> String {
+        let counts = self.counts(hand);
+        match self.categories.iter().find(|category| category.counts == counts) {
+            Some(category) => category.name.clone(),
+            None => {
+                let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+                counts.join("+")
+            }
+        }
+    }
+
+    /// Strengths of the cards of `hand`, comparing hands of the same
+    /// category.
+    pub fn tie_break(&self, hand: &str) -> Vec<usize> {
+        hand.chars().map(|card| self.strength(card).unwrap_or(0)).collect()
+    }
+
+    /// Key sorting hands from the weakest to the strongest.
+    fn key(&self, hand: &str) -> (Vec<usize>, Vec<usize>) {
+        (self.counts(hand), self.tie_break(hand))
+    }
+
+    /// `hand` with its wildcards replaced by the card they count as: the
+    /// most frequent other card, the strongest one on a tie.
+    pub fn substitution(&self, hand: &str) -> String {
+        let mut counts = std::collections::HashMap::new();
+        for card in hand.chars().filter(|card| !self.wildcards.contains(card)) {
+            *counts.entry(card).or_insert(0) += 1;
+        }
+        let best = counts.into_iter()
+            .max_by_key(|&(card, count)| (count, self.strength(card).unwrap_or(0)))
+            .map(|(card, _count)| card)
+            .or(self.order.iter().copied().find(|card| !self.wildcards.contains(card)));
+        match best {
+            None => hand.to_string(),
+            Some(best) =>
+                hand.chars().map(|card|
+                    if self.wildcards.contains(&card) { best } else { card }
+                ).collect(),
+        }
+    }
+
+    fn check_hand(&self, hand: &str) -> Result<()> {
+        if hand.chars().count() != self.hand_size {
+            Err(format!("Hand {hand} should have {} cards", self.hand_size))?
+        }
+        for card in hand.chars() {
+            self.strength(card)?;
+        }
+        Ok(())
+    }
+}
+
+/* Bids on a given hand: their number, their sum, and the sum of each bid
+   times the number of bids on the same hand read before it. */
+#[derive(Default)]
+pub struct Bids {
+    count: u128,
+    sum: u128,
+    weighted_sum: u128,
+}
+
+/* Hands with the lowest rank of their bids, weakest hand first. */
+fn rank_hands<'a>(
+    hand_bids: &'a std::collections::HashMap<String, Bids>, rules: &Rules
+) -> Vec<(&'a String, &'a Bids, u128)> {
+    let mut hands: Vec<_> = hand_bids.iter().collect();
+    hands.sort_by_cached_key(|(hand, _bids)| rules.key(hand));
+    let mut rank = 1;
+    hands.into_iter().map(|(hand, bids)| {
+        let first_rank = rank;
+        rank += bids.count;
+        (hand, bids, first_rank)
+    }).collect()
+}
+
+fn eval_hand_bids(
+    hand_bids: &std::collections::HashMap<String, Bids>, rules: &Rules
+) -> Result<u64> {
+    let total: u128 =
+        rank_hands(hand_bids, rules).into_iter().map(
+            /* Equal hands are ranked in reverse input order. */
+            |(_hand, bids, rank)|
+            (rank + bids.count - 1) * bids.sum - bids.weighted_sum
+        ).sum();
+    Ok(u64::try_from(total)?)
+}
+
+/* Ranks each row, given the hands ranked by `rank_hands`. */
+fn explain_ranks(
+    rows: &mut [Row], ranked_hands: Vec<(&String, &Bids, u128)>,
+    rank_field: &'static str, winnings_field: &'static str
+) {
+    let ranks: std::collections::HashMap<&String, (u128, u128)> =
+        ranked_hands.into_iter()
+        .map(|(hand, bids, rank)| (hand, (rank, bids.count))).collect();
+    let mut seen = std::collections::HashMap::new();
+    for row in rows {
+        let (Some(Value::Text(hand)), Some(&Value::Number(bid))) =
+            (row.get("hand").cloned(), row.get("bid"))
+        else { continue };
+        let Some(&(first_rank, count)) = ranks.get(&hand) else { continue };
+        let index = seen.entry(hand).or_insert(0);
+        let rank = first_rank + count - 1 - *index;
+        *index += 1;
+        row.set(rank_field, rank as u64);
+        row.set(winnings_field, Value::Number(rank as i128 * bid));
+    }
+}
+
+pub struct HandBid {
+    pub hand: String,
+    pub bid: u64,
+}
+
+impl std::fmt::Display for HandBid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.hand, self.bid)
+    }
+}
+
+/// Explanation columns of each part: type, rank and winnings.
+const FIELDS: [[&str; 3]; 2] = [
+    ["type 1", "rank 1", "part 1"],
+    ["type 2", "rank 2", "part 2"],
+];
+
+/// Solves a part for each of the rules, at most two.
+pub struct Day {
+    pub rules: Vec<Rules>,
+}
+
+impl Default for Day {
+    fn default() -> Self {
+        Self { rules: vec![Rules::part1(), Rules::part2()] }
+    }
+}
+
+impl LineSolver for Day {
+    type Line = HandBid;
+    type State = std::collections::HashMap<String, Bids>;
+
+    fn line(&self, line: &str) -> Result<HandBid> {
+        let (hand, bid) = line.split_once(' ').ok_or("Expected a hand and a bid")?;
+        for rules in &self.rules {
+            rules.check_hand(hand)?;
+        }
+        Ok(HandBid { hand: hand.to_string(), bid: bid.parse()? })
+    }
+
+    fn fold(
+        &self, state: &mut Self::State, HandBid { hand, bid }: HandBid
+    ) -> Result<()> {
+        let bids = state.entry(hand).or_default();
+        bids.weighted_sum += bids.count * bid as u128;
+        bids.count += 1;
+        bids.sum += bid as u128;
+        Ok(())
+    }
+
+    fn explain(&self, _state: &Self::State, line: &HandBid) -> Row {
+        let mut row = Row::new()
+            .with("hand", line.hand.as_str())
+            .with("bid", line.bid);
+        for (rules, [type_field, rank_field, winnings_field]) in self.rules.iter().zip(FIELDS) {
+            row.set(type_field, rules.category(&line.hand));
+            row.set(rank_field, 0_u64);
+            row.set(winnings_field, 0_u64);
+        }
+        row
+    }
+
+    fn complete_explanation(&self, hand_bids: &Self::State, rows: &mut [Row]) {
+        for (rules, [_, rank_field, winnings_field]) in self.rules.iter().zip(FIELDS) {
+            explain_ranks(rows, rank_hands(hand_bids, rules), rank_field, winnings_field);
+        }
+    }
+
+    fn finish(&self, hand_bids: Self::State) -> Result<Solution> {
+        let results: Vec<u64> = self.rules.iter()
+            .map(|rules| eval_hand_bids(&hand_bids, rules)).collect::<Result<_>>()?;
+        match results[..] {
+            [result_part1] => Ok(Solution::part1(result_part1)),
+            [result_part1, result_part2] => Ok(Solution::new(result_part1, result_part2)),
+            _ => Err("Expected rules for one or two parts")?,
+        }
+    }
+}
+
+pub fn read_hands(day: &Day, input: Input) -> Result<Vec<HandBid>> {
+    input.lines().enumerate().map(|(index, line)|
+        day.line(&line?).map_err(|Error(message)|
+            format!("Line {}: {message}", index + 1).into()
+        )
+    ).collect()
+}
+
+/// Rows of the hands ranked by `rules`, the weakest first.
+pub fn ranking(rules: &Rules, hands: &[HandBid]) -> Vec<Row> {
+    let mut order: Vec<usize> = (0 .. hands.len()).collect();
+    /* Equal hands are ranked in reverse input order. */
+    order.sort_by_cached_key(|&index|
+        (rules.key(&hands[index].hand), std::cmp::Reverse(index))
+    );
+    order.iter().enumerate().map(|(rank, &index)| {
+        let HandBid { hand, bid } = &hands[index];
+        let rank = rank as u64 + 1;
+        Row::new()
+            .with("hand", hand.as_str())
+            .with("bid", *bid)
+            .with("category", rules.category(hand))
+            .with("substitution", rules.substitution(hand))
+            .with("tie-break", rules.tie_break(hand))
+            .with("winnings", rank * bid)
+    }).collect()
+}
+
+/// Explains which of two hands wins under some rules, and why.
+pub struct Comparison<'a> {
+    rules: &'a Rules,
+    hands: [&'a str; 2],
+}
+
+impl<'a> Comparison<'a> {
+    pub fn new(rules: &'a Rules, hand1: &'a str, hand2: &'a str) -> Result<Self> {
+        rules.check_hand(hand1)?;
+        rules.check_hand(hand2)?;
+        Ok(Self { rules, hands: [hand1, hand2] })
+    }
+
+    fn describe(&self, hand: &str) -> String {
+        let counts: Vec<String> =
+            self.rules.counts(hand).iter().map(usize::to_string).collect();
+        let substitution = self.rules.substitution(hand);
+        let mut description =
+            format!("{hand} ({}, {}", self.rules.category(hand), counts.join("+"));
+        if substitution != hand {
+            description.push_str(&format!(" as {substitution}"));
+        }
+        description + ")"
+    }
+}
+
+impl std::fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let [hand1, hand2] = self.hands;
+        let ordering = self.rules.key(hand1).cmp(&self.rules.key(hand2));
+        let verb =
+            match ordering {
+                std::cmp::Ordering::Greater => "beats",
+                std::cmp::Ordering::Less => "loses to",
+                std::cmp::Ordering::Equal => "ties with",
+            };
+        write!(f, "{} {verb} {}: ", self.describe(hand1), self.describe(hand2))?;
+        /* The winner first. */
+        let [hand1, hand2] =
+            if ordering.is_lt() {
+                [hand2, hand1]
+            }
+            else {
+                [hand1, hand2]
+            };
+        let (category1, category2) = (self.rules.category(hand1), self.rules.category(hand2));
+        if self.rules.counts(hand1) != self.rules.counts(hand2) {
+            return write!(f, "{category1} beats {category2}");
+        }
+        let difference = hand1.chars().zip(hand2.chars()).enumerate()
+            .find(|(_, (card1, card2))| card1 != card2);
+        match difference {
+            None => write!(f, "same cards"),
+            Some((index, (card1, card2))) =>
+                write!(f, "both are {category1}, card {} {card1} beats {card2}", index + 1),
+        }
+    }
+}
+
+pub fn solve_with_rules(context: &Context, input: Input, day: &Day) -> Result<Solution> {
+    stream(day, context, input)
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    stream(&Day::default(), context, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+
+    fn rules_from(args: &[&str]) -> Result<Rules> {
+        let mut args = Args::new(args.iter().map(|arg| arg.to_string()));
+        let rules = Rules::from_args(&mut args)?.ok_or("Expected custom rules")?;
+        args.finish()?;
+        Ok(rules)
+    }
+
+    fn winnings(rules: Rules, input: &str) -> String {
+        let day = Day { rules: vec![rules] };
+        solve_with_rules(&Context::default(), Box::new(input.as_bytes()), &day).unwrap().part1
+    }
+
+    #[test]
+    fn example() {
+        let solution = solve(&Context::default(), Box::new(EXAMPLE.as_bytes())).unwrap();
+        assert_eq!(solution.part1, "6440");
+        assert_eq!(solution.part2.unwrap(), "5905");
+        assert!(rules_from(&[]).is_err());
+    }
+
+    /* J and Q both count as the most frequent other card. */
+    #[test]
+    fn two_wildcards() {
+        let rules = rules_from(&["--wildcards", "JQ"]).unwrap();
+        assert_eq!(rules.counts("QJ2A3"), [3, 1, 1]);
+        assert_eq!(rules.category("QQQJA"), "Five");
+        assert_eq!(rules.substitution("KTJQT"), "KTTTT");
+        assert_eq!(rules.substitution("JJQQJ"), "AAAAA");
+        /* QQQJA is now the strongest hand. */
+        assert_eq!(winnings(rules, EXAMPLE), "6168");
+        let Err(Error(message)) = rules_from(&["--wildcards", "JX"]) else {
+            panic!("X is not a card")
+        };
+        assert_eq!(message, "Wildcard X is not in the order");
+    }
+
+    #[test]
+    fn six_card_hands() {
+        let rules = rules_from(&[
+            "--hand-size", "6", "--order", "ABC",
+            "--category", "Six=6", "--category", "Pairs=2,2,2", "--category", "Triples=3,3",
+        ]).unwrap();
+        assert_eq!(rules.category("ABABAB"), "Triples");
+        assert_eq!(rules.category("AABBCC"), "Pairs");
+        /* Multiplicities without a category are named after them. */
+        assert_eq!(rules.category("AAAABC"), "4+1+1");
+        /* 3+3 beats 2+2+2, and A beats C. */
+        assert_eq!(winnings(rules, "AABBCC 1\nCCCBBB 10\nAAACCC 100\n"), "321");
+        let Err(Error(message)) = rules_from(&["--hand-size", "6", "--category", "Five=5"])
+        else {
+            panic!("Five cards are not a hand")
+        };
+        assert_eq!(message, "Category Five does not have 6 cards");
+        assert!(rules_from(&["--order", "AKA"]).is_err());
+        assert!(rules_from(&["--hand-size", "0"]).is_err());
+    }
+
+    /* Equal hands are ranked in reverse input order, as by the solver. */
+    #[test]
+    fn ranking_of_equal_hands() {
+        let input = "AAAAA 1\n23456 5\nAAAAA 10\n";
+        let day = Day::default();
+        let hands = read_hands(&day, Box::new(input.as_bytes())).unwrap();
+        let rows = ranking(&day.rules[0], &hands);
+        let ranked: Vec<(i128, i128)> = rows.iter().map(|row|
+            match (row.get("bid"), row.get("winnings")) {
+                (Some(&Value::Number(bid)), Some(&Value::Number(winnings))) => (bid, winnings),
+                _ => panic!("Missing bid or winnings"),
+            }
+        ).collect();
+        assert_eq!(ranked, [(5, 5), (10, 20), (1, 3)]);
+        assert_eq!(winnings(Rules::part1(), input), "28");
+    }
+
+    #[test]
+    fn ranking_of_the_example() {
+        let rules = Rules::part2();
+        let hands = read_hands(&Day::default(), Box::new(EXAMPLE.as_bytes())).unwrap();
+        let rows = ranking(&rules, &hands);
+        let row = &rows[4];
+        let text = |field| match row.get(field) {
+            Some(Value::Text(text)) => text.clone(),
+            _ => panic!("Missing {field}"),
+        };
+        assert_eq!(text("hand"), "KTJJT");
+        assert_eq!(text("category"), "Four");
+        assert_eq!(text("substitution"), "KTTTT");
+        let total: i128 = rows.iter().map(|row| match row.get("winnings") {
+            Some(&Value::Number(winnings)) => winnings,
+            _ => panic!("Missing winnings"),
+        }).sum();
+        assert_eq!(total, 5905);
+    }
+
+    #[test]
+    fn comparisons() {
+        let (part1, part2) = (Rules::part1(), Rules::part2());
+        let compare = |rules, hand1, hand2|
+            Comparison::new(rules, hand1, hand2).unwrap().to_string();
+        assert_eq!(
+            compare(&part1, "AAKQJ", "KKQQ2"),
+            "AAKQJ (One, 2+1+1+1) loses to KKQQ2 (Two, 2+2+1): Two beats One"
+        );
+        assert_eq!(
+            compare(&part2, "AAKQJ", "KKQQ2"),
+            "AAKQJ (Three, 3+1+1 as AAKQA) beats KKQQ2 (Two, 2+2+1): Three beats Two"
+        );
+        assert_eq!(
+            compare(&part1, "KTJJT", "KK677"),
+            "KTJJT (Two, 2+2+1) loses to KK677 (Two, 2+2+1): both are Two, card 2 K beats T"
+        );
+        assert_eq!(
+            compare(&part2, "QQQJA", "QQQJA"),
+            "QQQJA (Four, 4+1 as QQQQA) ties with QQQJA (Four, 4+1 as QQQQA): same cards"
+        );
+        assert!(Comparison::new(&part1, "AAKQ", "KKQQ2").is_err());
+        assert!(Comparison::new(&part1, "AAKQX", "KKQQ2").is_err());
+    }
+}
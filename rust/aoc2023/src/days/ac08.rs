@@ -1,7 +1,11 @@
-use advent_of_code::lcm;
+use std::io::BufRead;
 
-fn main() {
-    let mut lines = std::io::stdin().lines();
+use aoc_core::{Result, lcm, solver::{Context, Input, Solution}};
+
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let mut lines = input.lines();
     let directions = lines.next().unwrap().unwrap();
     let map: std::collections::HashMap<String, (String, String)> = lines.skip(1).map(
 	|line| {
@@ -16,17 +20,16 @@ fn main() {
     ).collect();
     let result_part1: u64 =
 	path_length(&map, &mut directions.chars().cycle(), "AAA", |place| place == "ZZZ");
-    let ghosts = map.keys().filter(|place| place.chars().last().unwrap() == 'A');
+    let ghosts = map.keys().filter(|place| place.ends_with('A'));
     // Not a general solution, but crafted for the kind of inputs AoC give...
     let result_part2: u64 =
 	ghosts.map(
 	    |place| path_length(
 		&map, &mut directions.chars().cycle(), place,
-		|place| place.chars().last().unwrap() == 'Z'
+		|place| place.ends_with('Z')
 	    )
 	).fold(1, lcm);
-    println!("Part 1: {result_part1}");
-    println!("Part 2: {result_part2}");
+    Ok(Solution::new(result_part1, result_part2))
 }
 
 fn path_length(
@@ -41,8 +44,8 @@ fn path_length(
 	let (left, right) = map.get(place).unwrap();
 	place =
 	    match directions.next().unwrap() {
-		'L' => &left,
-		'R' => &right,
+		'L' => left,
+		'R' => right,
 		_ => panic!("unknown direction"),
 	    };
 	count += 1;
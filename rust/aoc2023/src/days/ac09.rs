@@ -0,0 +1,79 @@
+use aoc_core::{
+    Result, explain::Row,
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+fn derive(numbers: &[i64]) -> Vec<i64> {
+    numbers.iter().zip(numbers.iter().skip(1)).map(|(a, b)| b - a).collect()
+}
+
+fn estimate(
+    numbers: &[i64],
+    side: &impl Fn(&[i64]) -> i64,
+    op: &impl Fn(i64, i64) -> i64
+) -> i64 {
+    if numbers.iter().all(|&n| n == 0) {
+	0
+    }
+    else {
+	let d = estimate(&derive(numbers), side, op);
+	op(side(numbers), d)
+    }
+}
+
+pub struct Extrapolation {
+    numbers: Vec<i64>,
+    next: i64,
+    previous: i64,
+}
+
+impl std::fmt::Display for Extrapolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.next, self.previous)
+    }
+}
+
+pub struct Day;
+
+impl LineSolver for Day {
+    type Line = Extrapolation;
+    type State = (i64, i64);
+
+    fn line(&self, line: &str) -> Result<Extrapolation> {
+	let numbers: Vec<i64> =
+	    line.split_whitespace().map(|s| Ok(s.parse()?)).collect::<Result<_>>()?;
+	if numbers.is_empty() {
+	    Err("Empty sequence")?
+	}
+	/* `estimate` only takes the side of sequences with a non-zero number. */
+	let next = estimate(&numbers, &|v| v[v.len() - 1], &|a, b| a + b);
+	let previous = estimate(&numbers, &|v| v[0], &|a, b| a - b);
+	Ok(Extrapolation { numbers, next, previous })
+    }
+
+    fn fold(&self, state: &mut (i64, i64), line: Extrapolation) -> Result<()> {
+	state.0 += line.next;
+	state.1 += line.previous;
+	Ok(())
+    }
+
+    fn finish(&self, (result_part1, result_part2): (i64, i64)) -> Result<Solution> {
+	Ok(Solution::new(result_part1, result_part2))
+    }
+
+    fn explain(&self, _state: &(i64, i64), line: &Extrapolation) -> Row {
+	let mut pyramid = vec![line.numbers.clone()];
+	while let Some(numbers) =
+	    pyramid.last().filter(|numbers| numbers.iter().any(|&n| n != 0)) {
+	    pyramid.push(derive(numbers));
+	}
+	Row::new()
+	    .with("previous", line.previous)
+	    .with("next", line.next)
+	    .with("differences", pyramid)
+    }
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    stream(&Day, context, input)
+}
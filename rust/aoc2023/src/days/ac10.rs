@@ -1,3 +1,7 @@
+use std::io::BufRead;
+
+use aoc_core::{Result, solver::{Context, Input, Solution}};
+
 fn follow_pipe(symbol: char, (dx, dy): (i64, i64)) -> Option<(i64, i64)> {
     match (symbol, dx, dy) {
         ('|', 0, _) => Some ((0, dy)),
@@ -34,12 +38,12 @@ fn add_offset((x, y): (i64, i64), (dx, dy): (i64, i64)) -> (i64, i64) {
     (x + dx, y + dy)
 }
 
-fn get_point(grid: &Vec<Vec<char>>, (x, y): (i64, i64)) -> char {
+fn get_point(grid: &[Vec<char>], (x, y): (i64, i64)) -> char {
     grid[y as usize][x as usize]
 }
 
 fn push_pipe(
-    pipes: &mut Vec<Vec<(i64, char)>>, (x, y): (i64, i64), symbol: char
+    pipes: &mut [Vec<(i64, char)>], (x, y): (i64, i64), symbol: char
 ) {
     if symbol == '-' {
         return;
@@ -54,8 +58,10 @@ enum State {
     Down,
 }
 
-fn main() {
-    let lines = std::io::stdin().lines();
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let lines = input.lines();
     let grid: Vec<Vec<_>> =
         lines.map(|line| line.unwrap().chars().collect()).collect();
     let width = grid.first().unwrap().len() as i64;
@@ -100,7 +106,6 @@ fn main() {
     len += 1;
     push_pipe(&mut pipes, position, infer_pipe(offset, first_offset));
     let half_len = len / 2;
-    println!("Part 1: {half_len}");
     let inside_area: i64 = pipes.into_iter().map(|mut pipes| {
         pipes.sort_by_key(|&(x, _)| x);
         let (total, _state) = pipes.iter().fold(
@@ -130,5 +135,5 @@ fn main() {
         );
         total
     }).sum();
-    println!("Part 2: {inside_area}")
+    Ok(Solution::new(half_len, inside_area))
 }
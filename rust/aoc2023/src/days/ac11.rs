@@ -0,0 +1,73 @@
+use std::io::BufRead;
+
+use aoc_core::{Result, Matrix2D, solver::{Context, Input, Solution}};
+
+fn partial_sums(it: impl Iterator<Item = u64>) -> impl Iterator<Item = u64> {
+    it.scan(0, |state, x| {
+	*state += x;
+	Some(*state)
+    })
+}
+
+pub fn distances(
+    galaxies: &[(usize, usize)],
+    empty_rows: &[u64],
+    empty_columns: &[u64],
+    factor: u64
+) -> u64 {
+    galaxies.iter().enumerate().map(|(i, &(x1, y1))|
+        galaxies[i+1..].iter().map(|&(x2, y2)|
+            (x1 as i64 - x2 as i64).unsigned_abs() +
+	    (y1 as i64 - y2 as i64).unsigned_abs() + 
+            (
+		(empty_rows[y1] as i64 - empty_rows[y2] as i64).abs() +
+		(empty_columns[x1] as i64 - empty_columns[x2] as i64).abs()
+	    ) as u64 * factor
+	  ).sum::<u64>()
+    ).sum()
+}
+
+pub struct Image {
+    pub galaxies: Vec<(usize, usize)>,
+    pub empty_rows: Vec<u64>,
+    pub empty_columns: Vec<u64>,
+}
+
+impl Image {
+    pub fn new(grid: &Matrix2D<bool>) -> Result<Self> {
+        let width = grid.first().ok_or("Empty image")?.len();
+        let empty_rows: Vec<_> =
+	    partial_sums(
+	        grid.iter().map(|line| line.iter().all(std::ops::Not::not) as u64)
+	    ).collect();
+        let empty_columns: Vec<_> =
+	    partial_sums(
+	        (0..width).map(|index|
+                    grid.iter().all(|line| !line[index]) as u64
+	        )
+	    ).collect();
+        let galaxies: Vec<_> =
+	    grid.iter().enumerate().flat_map(|(y, line)|
+              line.iter().enumerate().filter_map(move |(x, &galaxy)|
+                galaxy.then_some((x, y))
+	      )).collect();
+        Ok(Image { galaxies, empty_rows, empty_columns })
+    }
+
+    pub fn distances(&self, factor: u64) -> u64 {
+        distances(&self.galaxies, &self.empty_rows, &self.empty_columns, factor)
+    }
+}
+
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let grid: Vec<Vec<_>> =
+	input.lines().map(|line| -> Result<_> {
+            Ok(line?.chars().map(|c| c == '#').collect())
+        }).collect::<Result<_>>()?;
+    let image = Image::new(&grid)?;
+    let result_part1 = image.distances(1);
+    let result_part2 = image.distances(1000000 - 1);
+    Ok(Solution::new(result_part1, result_part2))
+}
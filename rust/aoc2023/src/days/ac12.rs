@@ -0,0 +1,198 @@
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, explain::Row, memory, parallel,
+    solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+#[derive(Clone, Copy)]
+pub enum Symbol {
+    Good,
+    Bad,
+    Unknown,
+}
+
+impl Symbol {
+    pub fn from_char(c: char) -> Option<Symbol> {
+        match c {
+            '.' => Some(Symbol::Good),
+            '#' => Some(Symbol::Bad),
+            '?' => Some(Symbol::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn can_be_good(&self) -> bool {
+        match self {
+            Symbol::Good | Symbol::Unknown => true,
+            Symbol::Bad => false,
+        }
+    }
+
+    pub fn can_be_bad(&self) -> bool {
+        match self {
+            Symbol::Bad | Symbol::Unknown => true,
+            Symbol::Good => false,
+        }
+    }
+}
+
+pub struct Instance {
+    pub damaged: Vec<Symbol>,
+    pub sequences: Vec<u64>,
+}
+
+impl Instance {
+    pub fn parse(line: &str) -> Result<Instance> {
+        let (damaged_str, sequences_str) =
+            line.split_once(' ').ok_or("Missing ' '")?;
+        let damaged: Vec<_> =
+            damaged_str.chars()
+            .map(|c| Symbol::from_char(c).ok_or(format!("Unknown symbol {c}")))
+            .collect::<std::result::Result<_, _>>()?;
+        let sequences: Vec<_> =
+            sequences_str.split(",").map(|s| s.parse())
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Instance { damaged, sequences })
+    }
+
+    fn align(
+        &self, memo: &mut std::collections::HashMap<(usize, u64, usize), u64>,
+        index_damaged: usize, previous_bad: u64, index_sequences: usize
+    ) -> u64 {
+        if let Some(&result) =
+            memo.get(&(index_damaged, previous_bad, index_sequences)) {
+                return result;
+        }
+        let result =
+            match self.damaged.get(index_damaged) {
+                None =>
+                    match (previous_bad, &self.sequences[index_sequences..]) {
+                        (0, &[]) => 1,
+                        (_, &[bad]) if previous_bad == bad => 1,
+                        _ => 0
+                    }
+                Some(symbol) => {
+                    let if_good =
+                        if symbol.can_be_good() {
+                            if previous_bad == 0 {
+                                self.align(
+                                    memo, index_damaged + 1, 0,
+                                    index_sequences
+                                )
+                            }
+                            else {
+                                match &self.sequences[index_sequences..] {
+                                    &[hd, ..] if hd == previous_bad => {
+                                        self.align(
+                                            memo, index_damaged + 1, 0,
+                                            index_sequences + 1
+                                        )
+                                    }
+                                    _ => 0
+                                }
+                            }
+                        }
+                        else { 0 };
+                    let if_bad =
+                        if symbol.can_be_bad() {
+                            match &self.sequences[index_sequences..] {
+                                &[hd, ..] if hd > previous_bad => {
+                                    self.align(
+                                        memo, index_damaged + 1,
+                                        previous_bad + 1, index_sequences
+                                    )
+                                }
+                                _ => 0
+                            }
+                        }
+                        else { 0 };
+                    if_good + if_bad
+                }
+            };
+        memo.insert((index_damaged, previous_bad, index_sequences), result);
+        result
+    }
+
+    pub fn count_alignments(&self) -> u64 {
+        let mut memo = std::collections::HashMap::new();
+        self.align(&mut memo, 0, 0, 0)
+    }
+
+    pub fn unfold(&self, times: usize) -> Self {
+        Instance {
+            damaged: self.damaged.iter().cloned().
+                chain(std::iter::once(Symbol::Unknown)).cycle().
+                take(self.damaged.len() * times + times - 1).collect(),
+            sequences: self.sequences.iter().cycle().
+                take(self.sequences.len() * times).cloned().collect(),
+        }
+    }
+}
+
+pub struct Arrangements {
+    folded: u64,
+    unfolded: u64,
+}
+
+impl std::fmt::Display for Arrangements {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.folded, self.unfolded)
+    }
+}
+
+pub struct Day;
+
+/* Rows are solved in parallel by batches, see `solver::stream`. */
+impl LineSolver for Day {
+    type Line = Arrangements;
+    type State = (u64, u64);
+
+    fn line(&self, line: &str) -> Result<Arrangements> {
+        let instance = Instance::parse(line)?;
+        Ok(Arrangements {
+            folded: instance.count_alignments(),
+            unfolded: instance.unfold(5).count_alignments(),
+        })
+    }
+
+    fn fold(&self, state: &mut (u64, u64), line: Arrangements) -> Result<()> {
+        state.0 += line.folded;
+        state.1 += line.unfolded;
+        Ok(())
+    }
+
+    fn finish(&self, (result_part1, result_part2): (u64, u64)) -> Result<Solution> {
+        Ok(Solution::new(result_part1, result_part2))
+    }
+
+    fn explain(&self, _state: &(u64, u64), line: &Arrangements) -> Row {
+        Row::new()
+            .with("part 1", line.folded)
+            .with("part 2", line.unfolded)
+    }
+}
+
+/* Rows are streamed, both parts at once. When allocations are counted, the
+   rows are read first and the parts solved one after the other instead, so
+   that their allocations can be told apart. Printing or explaining each row
+   still needs both parts row by row. */
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    if !memory::ENABLED || context.print_lines || context.explain.is_some() {
+        return stream(&Day, context, input);
+    }
+    let instances: Vec<Instance> = input.lines().enumerate().map(|(index, line)|
+        Instance::parse(&line?).map_err(|Error(message)|
+            format!("Line {}: {message}", index + 1).into()
+        )
+    ).collect::<Result<_>>()?;
+    memory::phase("part 1");
+    let result_part1: u64 = parallel::map(context.jobs, &instances, |instance|
+        instance.count_alignments()
+    ).iter().sum();
+    memory::phase("part 2");
+    let result_part2: u64 = parallel::map(context.jobs, &instances, |instance|
+        instance.unfold(5).count_alignments()
+    ).iter().sum();
+    Ok(Solution::new(result_part1, result_part2))
+}
@@ -0,0 +1,86 @@
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, explain::{self, Row}, parallel, solver::{Context, Input, Solution}
+};
+
+fn reflection_lines(
+    smudge_count: usize, size0: usize, size1: usize,
+    get: impl Fn(usize, usize) -> char
+) -> Vec<usize> {
+    (1 .. size0).filter(|&line| {
+        let mut allowed_defects = smudge_count;
+        (0 .. line.min(size0 - line)).all(|i|
+            (0 .. size1).all(|j|
+                get(line - i - 1, j) == get(line + i, j) || (
+                    allowed_defects > 0 && {
+                        allowed_defects -= 1;
+                        true
+                    }
+                )
+            )
+        ) && allowed_defects == 0
+    }).collect()
+}
+
+/* Columns and rows before which the pattern is reflected. */
+fn reflection_axes(
+    smudge_count: usize, pattern: &[Vec<char>]
+) -> (Vec<usize>, Vec<usize>) {
+    let height = pattern.len();
+    let width = pattern[0].len();
+    let vertical_lines =
+        reflection_lines(smudge_count, width, height, |i, j| pattern[j][i]);
+    let horizontal_lines =
+        reflection_lines(smudge_count, height, width, |i, j| pattern[i][j]);
+    (vertical_lines, horizontal_lines)
+}
+
+fn evaluate_reflection(smudge_count: usize, pattern: &[Vec<char>]) -> usize {
+    let (vertical_lines, horizontal_lines) =
+        reflection_axes(smudge_count, pattern);
+    vertical_lines.iter().sum::<usize>() +
+        horizontal_lines.iter().sum::<usize>() * 100
+}
+
+fn explain(pattern: &[Vec<char>]) -> Row {
+    let mut row = Row::new();
+    for (smudge_count, [columns, rows, total]) in
+        [(0, ["columns 1", "rows 1", "part 1"]), (1, ["columns 2", "rows 2", "part 2"])] {
+        let (vertical_lines, horizontal_lines) =
+            reflection_axes(smudge_count, pattern);
+        row.set(columns, vertical_lines);
+        row.set(rows, horizontal_lines);
+        row.set(total, evaluate_reflection(smudge_count, pattern));
+    }
+    row
+}
+
+pub fn solve(
+    context: &Context, input: Input
+) -> Result<Solution> {
+    let mut patterns = Vec::new();
+    let mut lines = input.lines();
+    loop {
+        let pattern: Vec<Vec<char>> =
+            lines.by_ref().take_while(|line| !line.as_ref().unwrap().is_empty()).
+            map(|line| line.unwrap().chars().collect()).collect();
+        if pattern.is_empty() {
+            break;
+        }
+        patterns.push(pattern);
+    }
+    if let Some(format) = context.explain {
+        let rows: Vec<Row> = patterns.iter().map(|pattern| explain(pattern)).collect();
+        explain::print(&mut std::io::stdout().lock(), format, "pattern", &rows)?;
+    }
+    let result_part1: usize =
+        parallel::map(context.jobs, &patterns, |pattern|
+            evaluate_reflection(0, pattern)
+        ).into_iter().sum();
+    let result_part2: usize =
+        parallel::map(context.jobs, &patterns, |pattern|
+            evaluate_reflection(1, pattern)
+        ).into_iter().sum();
+    Ok(Solution::new(result_part1, result_part2))
+}
@@ -1,3 +1,12 @@
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, animation::{Frame, Recorder}, memory, solver::{Context, Input, Solution},
+    trace
+};
+
+const TRACE: trace::Target = trace::Target("ac14");
+
 type Grid = Vec<Vec<char>>;
 
 fn drop_rounded_rocks(
@@ -50,13 +59,27 @@ fn tilt_north(grid: &mut Grid) {
     tilt(grid, height, width, &|i, j| (i, j));
 }
 
-fn tilt_cycle(grid: &mut Grid) {
+fn render(grid: &Grid) -> Frame {
+    Frame::from_matrix(grid, |c|
+        match c {
+            'O' => [230, 160, 40],
+            '#' => [110, 110, 110],
+            _ => [20, 20, 30],
+        }
+    )
+}
+
+fn tilt_cycle(grid: &mut Grid, recorder: &mut Recorder) {
     let height = grid.len();
     let width = grid[0].len();
     tilt(grid, height, width, &|i, j| (i, j));
+    recorder.record(|| render(grid));
     tilt(grid, width, height, &|i, j| (j, i));
+    recorder.record(|| render(grid));
     tilt(grid, height, width, &|i, j| (i, height - j - 1));
+    recorder.record(|| render(grid));
     tilt(grid, width, height, &|i, j| (width - j - 1, i));
+    recorder.record(|| render(grid));
 }
 
 fn amount_of_load(grid: &Grid) -> usize {
@@ -70,29 +93,39 @@ fn measure_cycle<'a>(
     grid: Grid,
     map: &mut std::collections::HashMap<&'a Grid, usize>,
     seq: &mut Vec<&'a Grid>,
+    recorder: &mut Recorder,
 ) -> (usize, usize) {
     let mut grid_ref = arena.alloc(grid);
+    recorder.record(|| render(grid_ref));
     loop {
         match map.entry(grid_ref) {
             std::collections::hash_map::Entry::Occupied(occupied) => {
+                TRACE.debug(|| format!(
+                    "grid after {} cycles already seen after {}: period {}",
+                    seq.len(), occupied.get(), seq.len() - occupied.get()
+                ));
                 return (*occupied.get(), seq.len());
             }
             std::collections::hash_map::Entry::Vacant(vacant) => {
+                TRACE.trace(|| format!(
+                    "cycle {}: load {}", seq.len(), amount_of_load(grid_ref)
+                ));
                 vacant.insert(seq.len());
             }
         }
         seq.push(grid_ref);
         let mut new_grid = grid_ref.clone();
-        tilt_cycle(&mut new_grid);
+        tilt_cycle(&mut new_grid, recorder);
         grid_ref = arena.alloc(new_grid);
     }
 }
 
-fn iterate_tilt(grid: Grid, count: usize) -> usize {
+fn iterate_tilt(grid: Grid, count: usize, recorder: &mut Recorder) -> usize {
     let arena = typed_arena::Arena::new();
     let mut map = std::collections::HashMap::new();
     let mut seq = Vec::new();
-    let (initial, cycle) = measure_cycle(&arena, grid, &mut map, &mut seq);
+    let (initial, cycle) =
+        measure_cycle(&arena, grid, &mut map, &mut seq, recorder);
     let grid =
         if count < cycle {
             seq[count]
@@ -104,14 +137,24 @@ fn iterate_tilt(grid: Grid, count: usize) -> usize {
     amount_of_load(grid)
 }
 
-fn main() {
-    let lines = std::io::stdin().lines();
+pub fn solve_recording(
+    _context: &Context, input: Input,
+    recorder: &mut Recorder
+) -> Result<Solution> {
+    let lines = input.lines();
     let grid: Grid =
         lines.map(|line| line.unwrap().chars().collect()).collect();
+    memory::phase("part 1");
     let mut grid_part1 = grid.clone();
     tilt_north(&mut grid_part1);
     let result_part1 = amount_of_load(&grid_part1);
-    println!("Part 1: {result_part1}");
-    let result_part2 = iterate_tilt(grid, 1000000000);
-    println!("Part 2: {result_part2}");
+    memory::phase("part 2");
+    let result_part2 = iterate_tilt(grid, 1000000000, recorder);
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(
+    context: &Context, input: Input
+) -> Result<Solution> {
+    solve_recording(context, input, &mut Recorder::disabled())
 }
@@ -1,3 +1,7 @@
+use std::io::BufRead;
+
+use aoc_core::{Result, solver::{Context, Input, Solution}};
+
 fn hash(s: &str) -> usize {
     s.chars().fold(0, |v, c| (v + c as usize) * 17 % 256)
 }
@@ -75,14 +79,15 @@ impl<'a> State<'a> {
     }
 }
 
-fn main() {
-    let mut input: String = String::new();
-    for line in std::io::stdin().lines() {
-        input.push_str(&line.unwrap())
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let mut sequence: String = String::new();
+    for line in input.lines() {
+        sequence.push_str(&line.unwrap())
     }
-    let instructions_str: Vec<_> = input.as_str().split(',').collect();
+    let instructions_str: Vec<_> = sequence.as_str().split(',').collect();
     let result_part1: usize = instructions_str.iter().cloned().map(hash).sum();
-    println!("Part 1: {result_part1}");
     let instructions = instructions_str.iter().cloned().map(Instruction::parse);
     let mut removed = std::collections::HashSet::new();
     let set_instructions: Vec<_> =
@@ -94,5 +99,5 @@ fn main() {
         set_instruction.execute(&mut state);
     }
     let result_part2: usize = state.evaluate();
-    println!("Part 2: {result_part2}");
+    Ok(Solution::new(result_part1, result_part2))
 }
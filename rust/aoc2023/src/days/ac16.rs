@@ -1,4 +1,10 @@
-use advent_of_code::{Result, Coords2D, Matrix2D, Zero, matrix_from_lines};
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, Coords2D, Matrix2D, Zero, matrix_from_lines, parallel,
+    animation::{Frame, Recorder},
+    solver::{Context, Input, Solution}
+};
 
 type Size = Coords2D<usize>;
 
@@ -76,16 +82,37 @@ impl EnergizedGrid {
             self.count += 1;
         }
     }
+
+    fn render(&self, grid: &Matrix2D<char>, beam: &Beam) -> Frame {
+        let mut frame = Frame::from_matrix(grid, |&c|
+            if c == '.' { [20, 20, 30] } else { [110, 110, 110] }
+        );
+        for (y, line) in self.grid.iter().enumerate() {
+            for (x, &energized) in line.iter().enumerate() {
+                if energized {
+                    let color =
+                        if grid[y][x] == '.' { [230, 200, 60] }
+                        else { [250, 240, 180] };
+                    frame.set(x, y, color);
+                }
+            }
+        }
+        frame.set(beam.position.x, beam.position.y, [230, 50, 40]);
+        frame
+    }
 }
 
-fn count_energized(grid: &Matrix2D<char>, initial: Beam) -> usize {
+fn count_energized(
+    grid: &Matrix2D<char>, initial: Beam, recorder: &mut Recorder
+) -> usize {
     let mut stack = VisitStack::new();
     stack.push(initial);
     let size = Size::from(grid);
     let mut energized = EnergizedGrid::new(size);
     while let Some(beam) = stack.pop() {
         energized.mark(beam.position);
-        match beam.position.get(&grid) {
+        recorder.record(|| energized.render(grid, &beam));
+        match beam.position.get(grid) {
             '.' => stack.push_opt(beam.advance(size, beam.direction)),
             '/' =>
                 stack.push_opt(
@@ -114,9 +141,9 @@ fn count_energized(grid: &Matrix2D<char>, initial: Beam) -> usize {
     energized.count
 }
 
-fn maximize_energy(grid: &Matrix2D<char>) -> usize {
+fn maximize_energy(context: &Context, grid: &Matrix2D<char>) -> usize {
     let size = Size::from(grid);
-    (0..size.y).flat_map(|y|
+    let initials: Vec<Beam> = (0..size.y).flat_map(|y|
         std::iter::once(
             Beam { position: Position { x: 0, y }, direction: Direction::RIGHT }
         ).chain(std::iter::once(
@@ -127,14 +154,24 @@ fn maximize_energy(grid: &Matrix2D<char>) -> usize {
             Beam { position: Position { x, y: 0 }, direction: Direction::DOWN }
         ).chain(std::iter::once(
             Beam { position: Position { x, y: size.y - 1 }, direction: Direction::UP }
-    )))).map(|initial| count_energized(&grid, initial)).max().unwrap()
+    )))).collect();
+    parallel::map(context.jobs, &initials, |&initial|
+        count_energized(grid, initial, &mut Recorder::disabled())
+    ).into_iter().max().unwrap()
+}
+
+pub fn solve_recording(
+    context: &Context, input: Input,
+    recorder: &mut Recorder
+) -> Result<Solution> {
+    let grid: Matrix2D<char> = matrix_from_lines(input.lines())?;
+    let result_part1 = count_energized(&grid, Beam::INITIAL, recorder);
+    let result_part2 = maximize_energy(context, &grid);
+    Ok(Solution::new(result_part1, result_part2))
 }
 
-fn main() -> Result<()> {
-    let grid: Matrix2D<char> = matrix_from_lines(std::io::stdin().lines())?;
-    let result_part1 = count_energized(&grid, Beam::INITIAL);
-    println!("Part 1: {result_part1}");
-    let result_part2 = maximize_energy(&grid);
-    println!("Part 2: {result_part2}");
-    Ok(())
+pub fn solve(
+    context: &Context, input: Input
+) -> Result<Solution> {
+    solve_recording(context, input, &mut Recorder::disabled())
 }
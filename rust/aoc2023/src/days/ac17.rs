@@ -1,8 +1,13 @@
-use advent_of_code::{
-    Error, Result, Matrix2D, Coords2D, Zero, matrix_from_lines
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, Matrix2D, Coords2D, Zero, matrix_from_lines,
+    solver::{Context, Input, Solution}, trace
 };
 
-#[derive(Clone, Copy,PartialEq, Eq, PartialOrd, Ord, Hash)]
+const TRACE: trace::Target = trace::Target("ac17");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum Direction {
     Horizontal,
     Vertical,
@@ -52,9 +57,19 @@ fn find_path(
     heat_losses.insert((from, Direction::Horizontal), 0);
     heap.push(State { heat_loss: 0, pos: from, dir: Direction::Vertical });
     heat_losses.insert((from, Direction::Vertical), 0);
+    let mut pop_count = 0;
     loop {
         let state = heap.pop().ok_or("Empty queue")?;
+        pop_count += 1;
+        TRACE.trace(|| format!(
+            "pop ({}, {}) {:?} heat loss {}, heap size {}",
+            state.pos.x, state.pos.y, state.dir, state.heat_loss, heap.len()
+        ));
         if state.pos == to {
+            TRACE.debug(|| format!(
+                "heat loss {} found after {pop_count} pops, {} positions reached",
+                state.heat_loss, heat_losses.len()
+            ));
             return Ok(state.heat_loss);
         }
         let heat_loss =
@@ -91,14 +106,20 @@ fn find_path(
                         }
                     }
                     heap.push(State { heat_loss, pos, dir });
+                    TRACE.trace(|| format!(
+                        "push ({}, {}) {dir:?} heat loss {heat_loss}, heap size {}",
+                        pos.x, pos.y, heap.len()
+                    ));
                 }
             }
         }
     }
 }
 
-fn main() -> Result<()> {
-    let char_grid = matrix_from_lines(std::io::stdin().lines())?;
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let char_grid = matrix_from_lines(input.lines())?;
     let grid: Matrix2D<u32> = char_grid.iter().map(|line|
       line.iter().map(|c| c.to_digit(10)
           .ok_or(Error::from(format!("Invalid digit {c}"))))
@@ -109,8 +130,6 @@ fn main() -> Result<()> {
         Coords2D::from(&grid).checked_add_signed(Coords2D::UP_LEFT)
         .ok_or("Empty grid")?;
     let result_part1 = find_path(&grid, 0, 3, from, to)?;
-    println!("Part 1: {result_part1}");
     let result_part2 = find_path(&grid, 4, 10, from, to)?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok(Solution::new(result_part1, result_part2))
 }
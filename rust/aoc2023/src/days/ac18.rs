@@ -0,0 +1,154 @@
+use aoc_core::{
+    Result, Coords2D, Zero, solver::{Context, Input, LineSolver, Solution, stream}
+};
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c =
+            match self {
+                Direction::Left => 'L',
+                Direction::Right => 'R',
+                Direction::Up => 'U',
+                Direction::Down => 'D',
+            };
+        write!(f, "{c}")
+    }
+}
+
+impl From<Direction> for Coords2D<i64> {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Left => Coords2D::LEFT,
+            Direction::Right => Coords2D::RIGHT,
+            Direction::Up => Coords2D::UP,
+            Direction::Down => Coords2D::DOWN,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Line {
+    direction: Direction,
+    count: u64,
+}
+
+fn parse_direction_part1(c: char) -> Result<Direction> {
+    match c {
+        'R' => Ok(Direction::Right),
+        'D' => Ok(Direction::Down),
+        'L' => Ok(Direction::Left),
+        'U' => Ok(Direction::Up),
+        _ => Err("Invalid direction character")?,
+    }
+}
+
+fn parse_direction_part2(c: char) -> Result<Direction> {
+    match c {
+        '0' => Ok(Direction::Right),
+        '1' => Ok(Direction::Down),
+        '2' => Ok(Direction::Left),
+        '3' => Ok(Direction::Up),
+        _ => Err("Invalid direction character")?,
+    }
+}
+
+fn parse_line(line: &str) -> Result<(Line, Line)> {
+    let mut parts = line.split(' ');
+    let direction_part1_chars: Vec<_> =
+        parts.next().ok_or("Missing direction")?.chars().collect();
+    let direction_part1 =
+        match &direction_part1_chars[..] {
+            &[c] => parse_direction_part1(c)?,
+            _ => return Err("Invalid direction symbol")?,
+        };
+    let count_part1 : u64 = parts.next().ok_or("Missing count")?.parse()?;
+    let line_part1 = Line { direction: direction_part1, count: count_part1 };
+    let color_part = parts.next().ok_or("Missing color part")?.
+        strip_prefix("(#").ok_or("Missing color prefix")?;
+    let color_chars_vec: Vec<_> = color_part.chars().collect();
+    let mut color_chars = color_chars_vec.iter();
+    let count_part2_str: String = color_chars.by_ref().take(5).collect();
+    let count_part2 = u64::from_str_radix(&count_part2_str, 16)?;
+    let direction_part2 : Direction =
+        parse_direction_part2(*color_chars.next().ok_or("Missing direction")?)?;
+    let line_part2 = Line { direction: direction_part2, count: count_part2 };
+    Ok((line_part1, line_part2))
+}
+
+/* Shoelace formula, computed as the lines are read. */
+pub struct Trench {
+    position: Coords2D<i64>,
+    twice_area: i64,
+    perimeter: u64,
+}
+
+impl Default for Trench {
+    fn default() -> Self {
+        Self { position: Coords2D::ZERO, twice_area: 0, perimeter: 0 }
+    }
+}
+
+impl Trench {
+    fn dig(&mut self, line: Line) {
+        let next =
+            self.position + Coords2D::from(line.direction) * (line.count as i64);
+        self.twice_area += Coords2D::det(self.position, next);
+        self.perimeter += line.count;
+        self.position = next;
+    }
+
+    fn area(&self) -> Result<u64> {
+        if self.perimeter == 0 {
+            Err("Empty coords")?
+        }
+        Ok((self.twice_area / 2).unsigned_abs() + self.perimeter / 2 + 1)
+    }
+}
+
+pub struct Instructions(Line, Line);
+
+impl std::fmt::Display for Instructions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "{} {} {} {}", self.0.direction, self.0.count,
+            self.1.direction, self.1.count
+        )
+    }
+}
+
+pub struct Day;
+
+impl LineSolver for Day {
+    type Line = Instructions;
+    type State = (Trench, Trench);
+
+    fn line(&self, line: &str) -> Result<Instructions> {
+        let (line_part1, line_part2) = parse_line(line)?;
+        Ok(Instructions(line_part1, line_part2))
+    }
+
+    fn fold(
+        &self, (part1, part2): &mut (Trench, Trench),
+        Instructions(line_part1, line_part2): Instructions
+    ) -> Result<()> {
+        part1.dig(line_part1);
+        part2.dig(line_part2);
+        Ok(())
+    }
+
+    fn finish(&self, (part1, part2): (Trench, Trench)) -> Result<Solution> {
+        Ok(Solution::new(part1.area()?, part2.area()?))
+    }
+}
+
+pub fn solve(context: &Context, input: Input) -> Result<Solution> {
+    stream(&Day, context, input)
+}
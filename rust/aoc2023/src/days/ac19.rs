@@ -1,4 +1,6 @@
-use advent_of_code::{Error, Result, NameTable, Name};
+use std::io::BufRead;
+
+use aoc_core::{Error, Result, NameTable, Name, solver::{Context, Input, Solution}};
 
 #[derive(Clone, Copy)]
 enum Category { X, M, A, S }
@@ -135,14 +137,14 @@ impl Workflows {
         it: &mut impl Iterator<Item = &'a String>,
     ) -> Result<Workflows> {
         Ok(Workflows { map:
-            it.map(|line| Workflow::parse(table, &line)).collect::<Result<_>>()?
+            it.map(|line| Workflow::parse(table, line)).collect::<Result<_>>()?
         })
     }
 
     fn get_answer(&self, mut name: Name, part: &Part<u64>) -> Result<Answer> {
         loop {
             let workflow = self.map.get(&name).ok_or("Unknown workflow")?;
-            match workflow.get_action(&part) {
+            match workflow.get_action(part) {
                 Action::Send(target) => name = target,
                 Action::Answer(answer) => return Ok(answer),
             }
@@ -297,8 +299,10 @@ impl Part<Range> {
     }
 }
 
-fn main() -> Result<()> {
-    let lines: Vec<_> = std::io::stdin().lines()
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let lines: Vec<_> = input.lines()
         .collect::<std::result::Result<_, _>>()?;
     let mut table = NameTable::new();
     let mut lines_iter = lines.iter();
@@ -312,13 +316,11 @@ fn main() -> Result<()> {
           part.is_accepted(&mut table, &workflows).map(|b| (part, b))).
         collect::<Result<_>>()?;
     let result_part1: u64 = accepted_vec.into_iter()
-        .filter_map(|(part, b)| b.then(|| part.sum())).sum();
-    println!("Part 1: {result_part1}");
+        .filter(|&(_part, b)| b).map(|(part, _b)| part.sum()).sum();
     let part2_range = Range { low: 1, high: 4000 };
     let initial = table.get("in");
     let result_part2: u64 =
         Part { x: part2_range, m: part2_range, a: part2_range, s: part2_range }
         .count_accepted(&mut table, &workflows, initial)?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok(Solution::new(result_part1, result_part2))
 }
@@ -1,4 +1,10 @@
-use advent_of_code::{Result, NameTable, Name, NameSet, NameMap, lcm};
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, NameTable, Name, NameSet, NameMap, lcm,
+    animation::{BLACK, WHITE, Color, Frame, Recorder},
+    solver::{Context, Input, Solution}
+};
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Pulse { Low, High }
@@ -15,6 +21,8 @@ trait RunningModule {
     fn receive(
         &mut self, message: &Message, send: &mut dyn FnMut(Pulse)
     );
+
+    fn color(&self) -> Color;
 }
 
 struct FlipFlop {}
@@ -58,6 +66,10 @@ impl RunningModule for RunningFlipFlop {
             self.state = !self.state;
         }
     }
+
+    fn color(&self) -> Color {
+        if self.state { [80, 200, 90] } else { [30, 70, 35] }
+    }
 }
 
 impl Module for Conjunction {
@@ -83,6 +95,10 @@ impl RunningModule for RunningConjunction {
         };
         send(if self.low_set.is_empty() { Pulse::Low } else { Pulse :: High })
     }
+
+    fn color(&self) -> Color {
+        if self.low_set.is_empty() { [230, 60, 50] } else { [90, 30, 30] }
+    }
 }
 
 impl Module for Broadcaster {
@@ -101,6 +117,10 @@ impl RunningModule for Broadcaster {
     fn receive(&mut self, message: &Message, send: &mut dyn FnMut(Pulse)) {
         send(message.pulse);
     }
+
+    fn color(&self) -> Color {
+        [80, 120, 230]
+    }
 }
 
 struct ModuleDescription<T> {
@@ -143,6 +163,29 @@ fn run(configuration: &Configuration) -> RunningConfiguration {
     }).collect()
 }
 
+fn render(configuration: &RunningConfiguration, message: &Message) -> Frame {
+    let mut names: Vec<_> = configuration.keys().cloned().collect();
+    names.sort();
+    let width = (1 ..).find(|width| width * width >= names.len()).unwrap_or(1);
+    let height = names.len().div_ceil(width).max(1);
+    let mut frame = Frame::new(width * 3, height * 3, BLACK);
+    for (index, name) in names.iter().enumerate() {
+        let (x, y) = (index % width * 3, index / width * 3);
+        if *name == message.destination {
+            let color =
+                match message.pulse {
+                    Pulse::Low => [80, 120, 230],
+                    Pulse::High => WHITE,
+                };
+            for i in 0 .. 9 {
+                frame.set(x + i % 3, y + i / 3, color);
+            }
+        }
+        frame.set(x + 1, y + 1, configuration[name].module.color());
+    }
+    frame
+}
+
 struct Message {
     input: Name,
     destination: Name,
@@ -185,13 +228,17 @@ impl<O: Observer> State<O> {
         );
     }
 
-    fn handle_messages(&mut self, configuration: &mut RunningConfiguration) {
+    fn handle_messages(
+        &mut self, configuration: &mut RunningConfiguration,
+        recorder: &mut Recorder
+    ) {
         while let Some(message) = self.message_queue.pop_front() {
             if let Some(description) = configuration.get_mut(&message.destination) {
                 description.module.receive(&message, &mut |pulse| {
                     self.send(message.destination, &description.destinations, pulse)
                 })
             }
+            recorder.record(|| render(configuration, &message));
         }
     }
 }
@@ -210,12 +257,15 @@ impl Observer for Counter {
     }
 }
 
-fn part1(table: &mut NameTable, configuration: &Configuration) -> u64 {
-    let mut running_configuration = run(&configuration);
+fn part1(
+    table: &mut NameTable, configuration: &Configuration,
+    recorder: &mut Recorder
+) -> u64 {
+    let mut running_configuration = run(configuration);
     let mut state = State::new(Counter { low: 0, high: 0 });
     for _i in 0 .. 1000 {
         state.push_button(table);
-        state.handle_messages(&mut running_configuration);
+        state.handle_messages(&mut running_configuration, recorder);
     }
     state.observer.low * state.observer.high
 }
@@ -243,23 +293,27 @@ fn part2(
     let inputs = configuration.get(&rx_input)
         .ok_or("rx input unconfigured")?
         .module.get_inputs().ok_or("rx input is not conjunction")?;
-    let mut running_configuration = run(&configuration);
+    let mut running_configuration = run(configuration);
     let mut state = State::new(FindFirstHighPulses {
-            counter: 0, inputs: inputs, indices: NameMap::new()
+            counter: 0, inputs, indices: NameMap::new()
         });
     while !state.observer.inputs.iter().all(|input|
         state.observer.indices.contains_key(input)
     ) {
         state.push_button(table);
         state.observer.counter += 1;
-        state.handle_messages(&mut running_configuration);
+        state.handle_messages(
+            &mut running_configuration, &mut Recorder::disabled());
     }
     Ok(state.observer.indices.values().cloned().fold(1, lcm))
 }
 
-fn main() -> Result<()> {
+pub fn solve_recording(
+    _context: &Context, input: Input,
+    recorder: &mut Recorder
+) -> Result<Solution> {
     let mut table = NameTable::new();
-    let mut configuration: Configuration = std::io::stdin().lines()
+    let mut configuration: Configuration = input.lines()
         .map(|line| -> Result<_> {
             ModuleDescription::parse(&mut table, &line?)
         }).collect::<Result<_>>()?;
@@ -281,10 +335,14 @@ fn main() -> Result<()> {
             }
         }
     }
-    let result_part1 = part1(&mut table, &configuration);
-    println!("Part 1: {result_part1}");
+    let result_part1 = part1(&mut table, &configuration, recorder);
     let rx_input = rx_input.ok_or("no input for rx")?;
     let result_part2 = part2(&mut table, &configuration, rx_input)?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(
+    context: &Context, input: Input
+) -> Result<Solution> {
+    solve_recording(context, input, &mut Recorder::disabled())
 }
@@ -1,5 +1,8 @@
-use advent_of_code::{
-    Result, Matrix2D, Coords2D, matrix_from_lines
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, Matrix2D, Coords2D, matrix_from_lines, memory,
+    solver::{Context, Input, Solution}
 };
 
 fn next_positions(
@@ -16,11 +19,13 @@ fn next_position_set(
     set.iter().flat_map(|&p| next_positions(&grid, p)).collect()
 }
 
-fn part1(grid: &Matrix2D<bool>, gardener: Coords2D<usize>) -> Result<usize> {
+pub fn part1(
+    grid: &Matrix2D<bool>, gardener: Coords2D<usize>, steps: usize
+) -> Result<usize> {
     let mut set = std::collections::HashSet::from([
         Coords2D::<isize>::try_from(gardener)?
     ]);
-    for _ in 0 .. 64 {
+    for _ in 0 .. steps {
         set = next_position_set(&|p|
             Coords2D::<usize>::try_from(p).is_ok_and(|p|
               p.try_get(grid).is_some_and(|&b| b)
@@ -39,13 +44,16 @@ fn modulus(a: isize, b: usize) -> usize {
     }
 }
 
-fn part2(grid: &Matrix2D<bool>, gardener: Coords2D<usize>) -> Result<usize> {
+/* Only valid when `steps % grid.len() == grid.len() / 2`. */
+pub fn part2(
+    grid: &Matrix2D<bool>, gardener: Coords2D<usize>, steps: usize
+) -> Result<usize> {
     let mut set = std::collections::HashSet::from([
         Coords2D::<isize>::try_from(gardener)?
     ]);
     let mut accu = Vec::new();
     let mut count = 0;
-    let n = 26501365 / grid.len();
+    let n = steps / grid.len();
     loop {
         if count % grid.len() == grid.len() / 2 {
             accu.push(set.len());
@@ -66,8 +74,9 @@ fn part2(grid: &Matrix2D<bool>, gardener: Coords2D<usize>) -> Result<usize> {
     }
 }
 
-fn main() -> Result<()> {
-    let char_grid = matrix_from_lines(std::io::stdin().lines())?;
+pub fn parse(
+    char_grid: Matrix2D<char>
+) -> Result<(Matrix2D<bool>, Coords2D<usize>)> {
     let gardener = char_grid.iter().enumerate().find_map(|(y, line)|
       line.iter().enumerate().find_map(|(x, &c)|
         (c == 'S').then_some(Coords2D { x, y })
@@ -77,9 +86,17 @@ fn main() -> Result<()> {
         char_grid.into_iter().map(|line|
             line.into_iter().map(|c| c == '.' || c == 'S').collect()
         ).collect();
-    let result_part1 = part1(&grid, gardener)?;
-    println!("Part 1: {result_part1}");
-    let result_part2 = part2(&grid, gardener)?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok((grid, gardener))
+}
+
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let char_grid = matrix_from_lines(input.lines())?;
+    let (grid, gardener) = parse(char_grid)?;
+    memory::phase("part 1");
+    let result_part1 = part1(&grid, gardener, 64)?;
+    memory::phase("part 2");
+    let result_part2 = part2(&grid, gardener, 26501365)?;
+    Ok(Solution::new(result_part1, result_part2))
 }
@@ -1,4 +1,10 @@
-use advent_of_code::{Error, Result, Coords3D};
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, Coords3D, parallel,
+    animation::{BLACK, Color, Frame, Recorder, palette_color},
+    solver::{Context, Input, Solution}
+};
 
 struct Block {
     inf: Coords3D<u64>,
@@ -49,17 +55,55 @@ fn filter_support(fallen: &Vec<FallenBlock>, block: &Block) -> (u64, Vec<usize>)
     (max_z, max_vec)
 }
 
-fn fall_blocks(blocks: &Vec<(usize, Block)>) -> Vec<FallenBlock> {
+fn render(
+    bounds: Coords3D<u64>, fallen: &[FallenBlock], falling: &[(usize, Block)]
+) -> Frame {
+    let width = bounds.x as usize + 1;
+    let depth = bounds.y as usize + 1;
+    let mut frame = Frame::new(width + 1 + depth, bounds.z as usize, BLACK);
+    let mut paint = |block: &Block, color: Color| {
+        for z in block.inf.z ..= block.sup.z {
+            let row = (bounds.z - z) as usize;
+            for x in block.inf.x ..= block.sup.x {
+                frame.set(x as usize, row, color);
+            }
+            for y in block.inf.y ..= block.sup.y {
+                frame.set(width + 1 + y as usize, row, color);
+            }
+        }
+    };
+    for (_id, block) in falling {
+        paint(block, [70, 70, 70]);
+    }
+    for fallen_block in fallen {
+        paint(&fallen_block.block, palette_color(fallen_block.id));
+    }
+    frame
+}
+
+fn fall_blocks(
+    blocks: &[(usize, Block)], recorder: &mut Recorder
+) -> Vec<FallenBlock> {
+    let bounds = blocks.iter().fold(
+        Coords3D { x: 0, y: 0, z: 0 },
+        |bounds, (_id, block)| Coords3D {
+            x: bounds.x.max(block.sup.x),
+            y: bounds.y.max(block.sup.y),
+            z: bounds.z.max(block.sup.z),
+        }
+    );
     let mut fallen = Vec::new();
-    for (id, block) in blocks {
-        let (z, support) = filter_support(&fallen, &block);
+    recorder.record(|| render(bounds, &fallen, blocks));
+    for (index, (id, block)) in blocks.iter().enumerate() {
+        let (z, support) = filter_support(&fallen, block);
         let z = z + 1;
         let height = block.sup.z - block.inf.z;
         let block = Block {
             inf: Coords3D { z, ..block.inf },
             sup: Coords3D { z: z + height, ..block.sup },
         };
-        fallen.push(FallenBlock { id: *id, block, support })
+        fallen.push(FallenBlock { id: *id, block, support });
+        recorder.record(|| render(bounds, &fallen, &blocks[index + 1 ..]));
     }
     fallen
 }
@@ -87,7 +131,7 @@ fn count_fall(
 }
 
 fn part2(
-    blocks: &Vec<FallenBlock>,
+    context: &Context, blocks: &Vec<FallenBlock>,
     singletons: std::collections::HashSet<usize>
 ) -> usize {
     let mut support = std::collections::HashMap::new();
@@ -100,16 +144,22 @@ fn part2(
                 .insert(fallen.id);
         }
     }
-    singletons.iter().map(|&id| count_fall(&support, &supported, id)).sum()
+    let singletons: Vec<usize> = singletons.into_iter().collect();
+    parallel::map(context.jobs, &singletons, |&id|
+        count_fall(&support, &supported, id)
+    ).into_iter().sum()
 }
 
-fn main() -> Result<()> {
-    let mut blocks: Vec<(usize, Block)> = std::io::stdin().lines()
+pub fn solve_recording(
+    context: &Context, input: Input,
+    recorder: &mut Recorder
+) -> Result<Solution> {
+    let mut blocks: Vec<(usize, Block)> = input.lines()
         .enumerate()
         .map(|(id, line)| -> Result<_> { Ok((id, line?.parse()?)) })
         .collect::<Result<_>>()?;
     blocks.sort_by_key(|(_, block)| block.inf.z);
-    let blocks = fall_blocks(&blocks);
+    let blocks = fall_blocks(&blocks, recorder);
     let singletons: std::collections::HashSet<_> = blocks.iter().filter_map(
         |fallen|
         match &fallen.support[..] {
@@ -118,8 +168,12 @@ fn main() -> Result<()> {
         }
     ).collect();
     let result_part1 = blocks.len() - singletons.len();
-    println!("Part 1: {result_part1}");
-    let result_part2 = part2(&blocks, singletons);
-    println!("Part 2: {result_part2}");
-    Ok(())
+    let result_part2 = part2(context, &blocks, singletons);
+    Ok(Solution::new(result_part1, result_part2))
+}
+
+pub fn solve(
+    context: &Context, input: Input
+) -> Result<Solution> {
+    solve_recording(context, input, &mut Recorder::disabled())
 }
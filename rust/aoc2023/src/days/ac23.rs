@@ -1,4 +1,11 @@
-use advent_of_code::{Result, Matrix2D, Coords2D, matrix_from_lines};
+use std::io::BufRead;
+
+use aoc_core::{
+    Result, Matrix2D, Coords2D, matrix_from_lines, solver::{Context, Input, Solution},
+    trace
+};
+
+const TRACE: trace::Target = trace::Target("ac23");
 
 enum Neighbor {
     OneWay(Coords2D<usize>),
@@ -25,7 +32,7 @@ fn follow_neighbor(
             return Some(Path { len, end: None });
         }
         let next =
-            match position.get(&neighbor_grid) {
+            match position.get(neighbor_grid) {
                 None => return None,
                 Some(Neighbor::OneWay(target)) => {
                     if *target == from {
@@ -73,7 +80,7 @@ impl IntersectionGraph {
                             Coords2D::<isize>::NEIGHBORS.iter()
                             .filter_map(|&direction|
                                 position.advance(size, direction)
-                                .filter(|position| *position.get(&grid) != '#'))
+                                .filter(|position| *position.get(grid) != '#'))
                             .collect();
                         match &neighbors[..] {
                             &[] | &[_] => None,
@@ -93,7 +100,7 @@ impl IntersectionGraph {
             &neighbor_grid, Coords2D { x: 1, y: 0 }, Coords2D { x: 1, y: 1 }
         ).ok_or("No initial path")?;
         let paths: Vec<Vec<_>> = intersections.iter().map(|(position, neighbors)| {
-            neighbors.into_iter().filter_map(|&neighbor| {
+            neighbors.iter().filter_map(|&neighbor| {
                 follow_neighbor(&neighbor_grid, *position, neighbor)
             }).collect()
         }).collect();
@@ -104,6 +111,10 @@ impl IntersectionGraph {
         &self, visited: &mut std::collections::HashSet<usize>, accu: usize, path: &Path
     ) -> Option<usize> {
         let accu = accu + path.len;
+        TRACE.trace(|| format!(
+            "depth {}: {} at distance {accu}", visited.len(),
+            path.end.map_or("exit".to_string(), |end| format!("intersection {end}"))
+        ));
         match path.end {
             None => Some(accu),
             Some(end) =>
@@ -122,19 +133,21 @@ impl IntersectionGraph {
     }
 
     fn search_longest_path(&self) -> Result<usize> {
-        Ok(self.search_longest_path_rec(
-            &mut std::collections::HashSet::new(), 0, &self.initial)
-            .ok_or("No path found")?)
+        let result = self.search_longest_path_rec(
+            &mut std::collections::HashSet::new(), 0, &self.initial
+        ).ok_or("No path found")?;
+        TRACE.debug(|| format!("longest path {result}"));
+        Ok(result)
     }
 }
 
-fn main() -> Result<()> {
-    let grid = matrix_from_lines(std::io::stdin().lines())?;
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let grid = matrix_from_lines(input.lines())?;
     let slippy_intersection_graph = IntersectionGraph::new(&grid, true)?;
     let result_part1 = slippy_intersection_graph.search_longest_path()?;
-    println!("Part 1: {result_part1}");
     let intersection_graph = IntersectionGraph::new(&grid, false)?;
     let result_part2 = intersection_graph.search_longest_path()?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok(Solution::new(result_part1, result_part2))
 }
@@ -1,4 +1,8 @@
-use advent_of_code::{Error, Result, Coords2D, Coords3D, Matrix2D};
+use std::io::BufRead;
+
+use aoc_core::{
+    Error, Result, Coords2D, Coords3D, Matrix2D, solver::{Context, Input, Solution}
+};
 
 struct Hailstone<T> {
     p: T,
@@ -50,11 +54,11 @@ impl std::str::FromStr for Hailstone<Coords3D<i64>> {
     }
 }
 
-fn part1(hailstones: &Vec<Hailstone<Coords3D<i64>>>) -> usize {
+fn part1(hailstones: &[Hailstone<Coords3D<i64>>]) -> usize {
     let (min, max) = (200000000000000., 400000000000000.);
     let xy: Vec<_> = hailstones.iter().map(|s| s.map(|c| c.xy())).collect();
     xy.iter().enumerate().map(|(i, s0)|
-        (&xy[i + 1 ..]).iter().filter(|s1|
+        xy[i + 1 ..].iter().filter(|s1|
             s0.as_f64().intersect(&s1.as_f64()).is_some_and(|p|
                 min <= p.x && p.x <= max && min <= p.y && p.y <= max)
         ).count()
@@ -126,14 +130,14 @@ fn gauss_jordan(matrix: &mut Matrix2D<f64>) -> Result<()> {
             )
         }
         pivot_row[column] = 1.;
-        for item in (&mut pivot_row[column + 1..]).iter_mut() {
+        for item in pivot_row[column + 1..].iter_mut() {
             *item /= pivot_value;
         }
         for row in before_row.iter_mut().chain(after_row.iter_mut()) {
             let k = row[column];
             row[column] = 0.;
             for (cell, pivot_cell) in
-                (&mut row[column + 1 ..]).iter_mut()
+                row[column + 1 ..].iter_mut()
                 .zip(&pivot_row[column + 1 ..]) {
                 *cell -= k * pivot_cell;
             }
@@ -155,7 +159,7 @@ fn matrix_line(
     ]
 }
 
-fn part2(hailstones: &Vec<Hailstone<Coords3D<i64>>>) -> Result<usize> {
+fn part2(hailstones: &[Hailstone<Coords3D<i64>>]) -> Result<usize> {
     let hailstones: Vec<_> =
         hailstones.iter().take(5).map(Hailstone::<Coords3D<i64>>::as_f64).collect();
     let mut matrix: Vec<_> =
@@ -176,13 +180,13 @@ fn part2(hailstones: &Vec<Hailstone<Coords3D<i64>>>) -> Result<usize> {
     Ok((x + y + z) as usize)
 }
 
-fn main() -> Result<()> {
-    let hailstones: Vec<Hailstone<Coords3D<i64>>> = std::io::stdin().lines()
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
+    let hailstones: Vec<Hailstone<Coords3D<i64>>> = input.lines()
         .map(|line| -> Result<_> { line?.parse() })
         .collect::<Result<_>>()?;
     let result_part1 = part1(&hailstones);
-    println!("Part 1: {result_part1}");
     let result_part2 = part2(&hailstones)?;
-    println!("Part 2: {result_part2}");
-    Ok(())
+    Ok(Solution::new(result_part1, result_part2))
 }
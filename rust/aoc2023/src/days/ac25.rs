@@ -1,6 +1,13 @@
-use advent_of_code::{Result, NameTable, Name, NameSet, NameMap};
+use std::io::BufRead;
 
-struct Graph(NameMap<NameSet>);
+use aoc_core::{
+    Result, NameTable, Name, NameSet, NameMap, solver::{Context, Input, Solution},
+    trace
+};
+
+const TRACE: trace::Target = trace::Target("ac25");
+
+pub struct Graph(NameMap<NameSet>);
 
 type EdgeSet = std::collections::HashSet<(Name, Name)>;
 
@@ -33,21 +40,27 @@ fn compute_path(
     result
 }
 
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Graph {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self(NameMap::new())
     }
 
     fn add_directed_edge(&mut self, u: Name, v: Name) {
-        self.0.entry(u).or_insert_with(NameSet::new).insert(v);
+        self.0.entry(u).or_default().insert(v);
     }
 
-    fn add_edge(&mut self, u: Name, v: Name) {
+    pub fn add_edge(&mut self, u: Name, v: Name) {
         self.add_directed_edge(u, v);
         self.add_directed_edge(v, u);
     }
 
-    fn parse_and_add_edges(
+    pub fn parse_and_add_edges(
         &mut self, table: &mut NameTable, s: &str
     ) -> Result<()> {
         let (src_str, rhs) = s.split_once(": ").ok_or("No ': ' found")?;
@@ -59,11 +72,11 @@ impl Graph {
         Ok(())
     }
 
-    fn nodes(&self) -> impl Iterator<Item = Name> + '_ {
+    pub fn nodes(&self) -> impl Iterator<Item = Name> + '_ {
         self.0.keys().cloned()
     }
 
-    fn neighbors(&self, node: Name) -> Option<&NameSet> {
+    pub fn neighbors(&self, node: Name) -> Option<&NameSet> {
         self.0.get(&node)
     }
 
@@ -118,7 +131,7 @@ impl Graph {
                 }
                 pred.insert(v, u);
                 if succ.contains_key(&v) {
-                    return AddNeighbors::Path(compute_path(v, &pred, succ));
+                    return AddNeighbors::Path(compute_path(v, pred, succ));
                 }
                 q.push(v);
             }
@@ -134,13 +147,21 @@ impl Graph {
         let mut succ = NameMap::from([(t, t)]);
         let mut q_t = vec![t];
         loop {
+            TRACE.trace(|| format!(
+                "queue sizes {} from the source, {} from the sink",
+                q_s.len(), q_t.len()
+            ));
             if q_s.len() < q_t.len() {
                 match self.add_neighbors(
                     |u, v| flow.contains(&(u, v)), &succ, &mut q_s, &mut pred
                 ) {
-                    AddNeighbors::Path(p) => return Some(p),
+                    AddNeighbors::Path(p) => {
+                        TRACE.trace(|| format!("path of length {}", p.len()));
+                        return Some(p)
+                    }
                     AddNeighbors::NextQueue(q) => {
                         if q.is_empty() {
+                            TRACE.trace(|| "no path".to_string());
                             return None;
                         }
                         q_s = q;
@@ -152,11 +173,13 @@ impl Graph {
                     |u, v| flow.contains(&(v, u)), &pred, &mut q_t, &mut succ
                 ) {
                     AddNeighbors::Path(mut p) => {
+                        TRACE.trace(|| format!("path of length {}", p.len()));
                         p.reverse();
                         return Some(p)
                     }
                     AddNeighbors::NextQueue(q) => {
                         if q.is_empty() {
+                            TRACE.trace(|| "no path".to_string());
                             return None;
                         }
                         q_t = q;
@@ -173,10 +196,11 @@ impl Graph {
             flow.extend(path.iter().cloned().zip(path.iter().skip(1).cloned()));
             value += 1;
         }
+        TRACE.debug(|| format!("maximum flow {value}"));
         (value, flow)
     }
 
-    fn minimum_edge_cut(&self) -> Result<(NameSet, NameSet)> {
+    pub fn minimum_edge_cut(&self) -> Result<(NameSet, NameSet)> {
         match self.nodes().find_map(|node|
             Some(self.dominating_set(node)).filter(|set| set.len() >= 2)
         ) {
@@ -206,15 +230,15 @@ impl Graph {
     }
 }
 
-
-fn main() -> Result<()> {
+pub fn solve(
+    _context: &Context, input: Input
+) -> Result<Solution> {
     let mut table = NameTable::new();
     let mut graph = Graph::new();
-    for line in std::io::stdin().lines() {
+    for line in input.lines() {
         graph.parse_and_add_edges(&mut table, &line?)?;
     }
     let (group1, group2) = graph.minimum_edge_cut()?;
     let result_part1 = group1.len() * group2.len();
-    println!("Part 1: {result_part1}");
-    Ok(())
+    Ok(Solution::part1(result_part1))
 }
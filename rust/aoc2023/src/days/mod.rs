@@ -0,0 +1,55 @@
+use aoc_core::solver::Solver;
+
+pub mod ac01;
+pub mod ac02;
+pub mod ac03;
+pub mod ac04;
+pub mod ac05;
+pub mod ac06;
+pub mod ac07;
+pub mod ac08;
+pub mod ac09;
+pub mod ac10;
+pub mod ac11;
+pub mod ac12;
+pub mod ac13;
+pub mod ac14;
+pub mod ac15;
+pub mod ac16;
+pub mod ac17;
+pub mod ac18;
+pub mod ac19;
+pub mod ac20;
+pub mod ac21;
+pub mod ac22;
+pub mod ac23;
+pub mod ac24;
+pub mod ac25;
+
+pub const SOLVERS: &[&dyn Solver] = &[
+    &ac01::solve,
+    &ac02::solve,
+    &ac03::solve,
+    &ac04::solve,
+    &ac05::solve,
+    &ac06::solve,
+    &ac07::solve,
+    &ac08::solve,
+    &ac09::solve,
+    &ac10::solve,
+    &ac11::solve,
+    &ac12::solve,
+    &ac13::solve,
+    &ac14::solve,
+    &ac15::solve,
+    &ac16::solve,
+    &ac17::solve,
+    &ac18::solve,
+    &ac19::solve,
+    &ac20::solve,
+    &ac21::solve,
+    &ac22::solve,
+    &ac23::solve,
+    &ac24::solve,
+    &ac25::solve,
+];
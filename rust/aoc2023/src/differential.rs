@@ -0,0 +1,451 @@
+//! Differential testing: the fast solvers of `crate::days` are run against
+//! the reference solvers of `crate::reference` on small generated inputs.
+//! When they disagree, the input is shrunk while they keep disagreeing.
+
+use aoc_core::{Error, Result, rng::Rng};
+use crate::{days, generators, reference};
+
+struct Check {
+    day: u32,
+    max_size: usize,
+    generate: fn(&mut Rng, usize) -> Vec<String>,
+    fast: fn(&str) -> Result<String>,
+    reference: fn(&str) -> Result<String>,
+    shrink: fn(&str) -> Vec<String>,
+}
+
+/// A day may have several checks, against different reference solvers.
+const CHECKS: [Check; 8] = [
+    Check {
+        day: 4,
+        max_size: 12,
+        generate: generators::ac04::generate,
+        fast: fast_ac04,
+        reference: reference_ac04,
+        shrink: shrink_lines,
+    },
+    Check {
+        day: 6,
+        max_size: 3,
+        generate: generators::ac06::generate,
+        fast: fast_ac06,
+        reference: reference_ac06,
+        shrink: shrink_races,
+    },
+    Check {
+        day: 6,
+        max_size: 6,
+        generate: generators::ac06::generate,
+        fast: fast_ac06,
+        reference: float_ac06,
+        shrink: shrink_races,
+    },
+    Check {
+        day: 6,
+        max_size: 6,
+        generate: generators::ac06::generate,
+        fast: fast_ac06,
+        reference: bisection_ac06,
+        shrink: shrink_races,
+    },
+    Check {
+        day: 11,
+        max_size: 10,
+        generate: generators::ac11::generate,
+        fast: fast_ac11,
+        reference: reference_ac11,
+        shrink: shrink_grid,
+    },
+    Check {
+        day: 12,
+        max_size: 6,
+        generate: generate_ac12,
+        fast: fast_ac12,
+        reference: reference_ac12,
+        shrink: shrink_lines,
+    },
+    Check {
+        day: 21,
+        max_size: 4,
+        generate: generate_ac21,
+        fast: fast_ac21,
+        reference: reference_ac21,
+        shrink: shrink_rocks,
+    },
+    Check {
+        day: 25,
+        max_size: 5,
+        generate: generate_ac25,
+        fast: fast_ac25,
+        reference: reference_ac25,
+        shrink: shrink_edges,
+    },
+];
+
+fn fast_ac04(input: &str) -> Result<String> {
+    let cards: Vec<_> = input.lines().map(days::ac04::winning_numbers_count)
+        .collect::<Result<_>>()?;
+    Ok(format!("{} {}", days::ac04::part1(&cards), days::ac04::part2(&cards)))
+}
+
+fn reference_ac04(input: &str) -> Result<String> {
+    let matches = reference::ac04::matches(input)?;
+    if matches.is_empty() {
+        Err("No card")?
+    }
+    Ok(format!(
+        "{} {}", reference::ac04::part1(&matches), reference::ac04::part2(&matches)
+    ))
+}
+
+fn fast_ac06(input: &str) -> Result<String> {
+    let (times_str, distances_str) = days::ac06::parse(input)?;
+    let physics = days::ac06::Physics::default();
+    Ok(format!(
+        "{} {}", days::ac06::part1(times_str, distances_str, &physics)?,
+        days::ac06::part2(times_str, distances_str, &physics)?
+    ))
+}
+
+/// Counts the winning holds found by bisection, which the solver only uses
+/// for other physics, on races of any size.
+fn bisection_ac06(input: &str) -> Result<String> {
+    let physics = days::ac06::Physics::default();
+    let number_ways = |time, distance| -> u64 {
+        physics.winning_holds(time, distance).iter()
+            .map(|(start, end)| end - start + 1).sum()
+    };
+    let mut races = reference::ac06::races(input)?;
+    let (time, distance) = races.pop().ok_or("No race")?;
+    let part1: u64 =
+        races.iter().map(|&(time, distance)| number_ways(time, distance)).product();
+    Ok(format!("{part1} {}", number_ways(time, distance)))
+}
+
+/* The race of Part 2 should be winnable and small enough to be tried
+   exhaustively. */
+const MAX_TIME: u64 = 1_000_000;
+
+fn reference_ac06(input: &str) -> Result<String> {
+    let mut races = reference::ac06::races(input)?;
+    let (time, distance) = races.pop().ok_or("No race")?;
+    if time > MAX_TIME || races.is_empty() {
+        Err("Race too long")?
+    }
+    let part1: u64 = races.iter().map(|&(time, distance)|
+        reference::ac06::number_ways(time, distance)
+    ).product();
+    let part2 = reference::ac06::number_ways(time, distance);
+    if part2 == 0 {
+        Err("Race cannot be won")?
+    }
+    Ok(format!("{part1} {part2}"))
+}
+
+/* Beyond 2^53, f64 no longer represents every integer. */
+const MAX_FLOAT_EXACT: u64 = 1 << 53;
+
+fn float_ac06(input: &str) -> Result<String> {
+    let mut races = reference::ac06::races(input)?;
+    let (time, distance) = races.pop().ok_or("No race")?;
+    if distance >= MAX_FLOAT_EXACT / 4 || races.is_empty() {
+        Err("Race too long")?
+    }
+    let part1: u64 = races.iter().map(|&(time, distance)|
+        reference::ac06::number_ways_float(time, distance)
+    ).product();
+    let part2 = reference::ac06::number_ways_float(time, distance);
+    Ok(format!("{part1} {part2}"))
+}
+
+const EXPANSIONS: [usize; 3] = [1, 2, 10];
+
+fn fast_ac11(input: &str) -> Result<String> {
+    let grid: Vec<Vec<_>> =
+        input.lines().map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    let image = days::ac11::Image::new(&grid)?;
+    Ok(generators::join(EXPANSIONS.iter().map(|&expansion|
+        image.distances(expansion as u64 - 1)
+    ), " "))
+}
+
+fn reference_ac11(input: &str) -> Result<String> {
+    Ok(generators::join(EXPANSIONS.iter().map(|&expansion|
+        reference::ac11::distances(input, expansion)
+    ).collect::<Result<Vec<_>>>()?, " "))
+}
+
+/* Unfolding doubles the number of unknown springs. */
+fn generate_ac12(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    while lines.len() < size {
+        lines.extend(generators::ac12::generate(rng, 1).into_iter().filter(|line|
+            line.matches('?').count() < reference::ac12::MAX_UNKNOWNS / 2
+        ));
+    }
+    lines
+}
+
+const FOLDS: [usize; 2] = [1, 2];
+
+fn fast_ac12(input: &str) -> Result<String> {
+    let instances: Vec<_> = input.lines().map(days::ac12::Instance::parse)
+        .collect::<Result<_>>()?;
+    Ok(generators::join(FOLDS.iter().map(|&times|
+        instances.iter().map(|instance| instance.unfold(times).count_alignments())
+        .sum::<u64>()
+    ), " "))
+}
+
+fn reference_ac12(input: &str) -> Result<String> {
+    Ok(generators::join(FOLDS.iter().map(|&times|
+        reference::ac12::total(input, times)
+    ).collect::<Result<Vec<_>>>()?, " "))
+}
+
+fn generate_ac21(rng: &mut Rng, size: usize) -> Vec<String> {
+    generators::ac21::garden(rng, 2 * size + 5)
+}
+
+/* Part 1 on the finite map, Part 2 at the middle of the 3rd and 4th copies
+   of the map, so that the quadratic extrapolation is actually used. */
+fn steps_ac21(side: usize) -> [usize; 3] {
+    [side, 3 * side + side / 2, 4 * side + side / 2]
+}
+
+fn fast_ac21(input: &str) -> Result<String> {
+    let char_grid = input.lines().map(|line| line.chars().collect()).collect();
+    let (grid, gardener) = days::ac21::parse(char_grid)?;
+    let [steps1, steps2, steps3] = steps_ac21(grid.len());
+    Ok(format!(
+        "{} {} {}", days::ac21::part1(&grid, gardener, steps1)?,
+        days::ac21::part2(&grid, gardener, steps2)?,
+        days::ac21::part2(&grid, gardener, steps3)?
+    ))
+}
+
+fn reference_ac21(input: &str) -> Result<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    if lines.iter().any(|line| line.len() != lines.len()) || lines.len().is_multiple_of(2) {
+        Err("Map should be a square of odd side")?
+    }
+    let [steps1, steps2, steps3] = steps_ac21(lines.len());
+    Ok(format!(
+        "{} {} {}", reference::ac21::reachable(input, steps1, false)?,
+        reference::ac21::reachable(input, steps2, true)?,
+        reference::ac21::reachable(input, steps3, true)?
+    ))
+}
+
+fn generate_ac25(rng: &mut Rng, size: usize) -> Vec<String> {
+    generators::ac25::generate(rng, 9 + size)
+}
+
+fn fast_ac25(input: &str) -> Result<String> {
+    let mut table = aoc_core::NameTable::new();
+    let mut graph = days::ac25::Graph::new();
+    for line in input.lines() {
+        graph.parse_and_add_edges(&mut table, line)?;
+    }
+    let (group1, group2) = graph.minimum_edge_cut()?;
+    Ok((group1.len() * group2.len()).to_string())
+}
+
+fn reference_ac25(input: &str) -> Result<String> {
+    Ok(reference::ac25::minimum_cut_product(input)?.to_string())
+}
+
+/// Copies of `items` where a chunk has been removed, largest chunks first.
+fn remove_chunks<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut result = Vec::new();
+    let mut chunk = items.len() / 2;
+    while chunk > 0 {
+        for start in (0 .. items.len()).step_by(chunk) {
+            let mut smaller = items[.. start].to_vec();
+            smaller.extend_from_slice(&items[(start + chunk).min(items.len()) ..]);
+            result.push(smaller);
+        }
+        chunk /= 2;
+    }
+    result
+}
+
+fn unlines(lines: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    lines.into_iter().map(|line| format!("{}\n", line.as_ref())).collect()
+}
+
+fn shrink_lines(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    remove_chunks(&lines).into_iter().map(unlines).collect()
+}
+
+fn shrink_races(input: &str) -> Vec<String> {
+    let lines: Vec<(&str, Vec<&str>)> = input.lines().map(|line| {
+        let (prefix, numbers) = line.split_once(':').unwrap_or((line, ""));
+        (prefix, numbers.split_whitespace().collect())
+    }).collect();
+    let count = lines.iter().map(|(_, numbers)| numbers.len()).max().unwrap_or(0);
+    let races: Vec<usize> = (0 .. count).collect();
+    remove_chunks(&races).into_iter().map(|kept|
+        unlines(lines.iter().map(|(prefix, numbers)| format!(
+            "{prefix}: {}",
+            generators::join(kept.iter().filter_map(|&race| numbers.get(race)), " ")
+        )))
+    ).collect()
+}
+
+fn shrink_grid(input: &str) -> Vec<String> {
+    let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let columns: Vec<usize> = (0 .. width).collect();
+    let mut result = shrink_lines(input);
+    result.extend(remove_chunks(&columns).into_iter().map(|kept|
+        unlines(rows.iter().map(|row|
+            kept.iter().filter_map(|&x| row.get(x)).collect::<String>()
+        ))
+    ));
+    result
+}
+
+fn shrink_rocks(input: &str) -> Vec<String> {
+    let rocks: Vec<usize> = input.char_indices()
+        .filter(|&(_, c)| c == '#').map(|(index, _)| index).collect();
+    remove_chunks(&rocks).into_iter().map(|kept|
+        input.char_indices().map(|(index, c)|
+            if c == '#' && !kept.contains(&index) { '.' } else { c }
+        ).collect()
+    ).collect()
+}
+
+fn shrink_edges(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut result = shrink_lines(input);
+    for (index, line) in lines.iter().enumerate() {
+        let Some((source, targets_str)) = line.split_once(": ") else { continue };
+        let targets: Vec<&str> = targets_str.split(' ').collect();
+        for kept in remove_chunks(&targets) {
+            if kept.is_empty() {
+                continue;
+            }
+            let mut smaller = lines.clone();
+            let line = format!("{source}: {}", kept.join(" "));
+            smaller[index] = &line;
+            result.push(unlines(smaller));
+        }
+    }
+    result
+}
+
+enum Outcome {
+    Agree,
+    /// The reference solver rejected the input.
+    Invalid,
+    Disagree { fast: String, reference: String },
+}
+
+fn compare(check: &Check, input: &str) -> Outcome {
+    let Ok(reference) = (check.reference)(input) else { return Outcome::Invalid };
+    let fast =
+        match std::panic::catch_unwind(|| (check.fast)(input)) {
+            Ok(Ok(fast)) => fast,
+            Ok(Err(Error(message))) => format!("error: {message}"),
+            Err(_) => "panic".to_string(),
+        };
+    if fast == reference {
+        Outcome::Agree
+    }
+    else {
+        Outcome::Disagree { fast, reference }
+    }
+}
+
+pub struct Failure {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub fast: String,
+    pub reference: String,
+}
+
+pub struct Report {
+    pub day: u32,
+    pub passed: usize,
+    pub skipped: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// Greedily applies the shrinking steps that keep the solvers disagreeing.
+fn shrink(check: &Check, mut failure: Failure) -> Failure {
+    'shrink: loop {
+        for input in (check.shrink)(&failure.input) {
+            if let Outcome::Disagree { fast, reference } = compare(check, &input) {
+                failure = Failure { input, fast, reference, ..failure };
+                continue 'shrink;
+            }
+        }
+        return failure;
+    }
+}
+
+pub fn days() -> impl Iterator<Item = u32> {
+    let mut days: Vec<u32> = CHECKS.iter().map(|check| check.day).collect();
+    days.dedup();
+    days.into_iter()
+}
+
+/// Runs `cases` inputs, with seeds from `seed` and sizes cycling up to the
+/// largest size the reference solver can handle, for each check of the day.
+pub fn check(day: u32, seed: u64, cases: usize) -> Result<Report> {
+    let checks: Vec<&Check> = CHECKS.iter().filter(|check| check.day == day).collect();
+    if checks.is_empty() {
+        Err(format!("No reference solver for day {day}"))?
+    }
+    let mut report = Report { day, passed: 0, skipped: 0, failures: Vec::new() };
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    for check in checks {
+        for case in 0 .. cases {
+            let seed = seed + case as u64;
+            let size = 1 + case % check.max_size;
+            let input = unlines((check.generate)(&mut Rng::new(seed), size));
+            match compare(check, &input) {
+                Outcome::Agree => report.passed += 1,
+                Outcome::Invalid => report.skipped += 1,
+                Outcome::Disagree { fast, reference } => {
+                    let failure = Failure { seed, size, input, fast, reference };
+                    report.failures.push(shrink(check, failure));
+                }
+            }
+        }
+    }
+    std::panic::set_hook(hook);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXED_SEED: u64 = 0;
+    /// Few cases, as the reference solvers are slow in debug builds.
+    const CASES: usize = 10;
+
+    /* The days are checked one after the other, as `check` swaps the panic
+       hook. */
+    #[test]
+    fn fast_solvers_agree_with_references() {
+        assert_eq!(days().collect::<Vec<_>>(), [4, 6, 11, 12, 21, 25]);
+        for day in days() {
+            let report = check(day, FIXED_SEED, CASES).unwrap();
+            assert!(report.passed > 0, "Day {day}: no case passed");
+            if let Some(failure) = report.failures.first() {
+                panic!(
+                    "Day {day}, seed {}, size {}: {} instead of {} on\n{}",
+                    failure.seed, failure.size, failure.fast, failure.reference,
+                    failure.input
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_core::rng::Rng;
+
+const WORDS: [&str; 9] =
+    ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+fn digit(rng: &mut Rng) -> char {
+    char::from(b'0' + rng.range(1 .. 10) as u8)
+}
+
+/// `size` calibration lines, each containing at least one digit.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_| {
+        let mut line = String::new();
+        for _ in 0 .. rng.range(1 .. 6) {
+            match rng.below(3) {
+                0 => line.push(digit(rng)),
+                1 => line.push_str(WORDS[rng.index(WORDS.len())]),
+                _ =>
+                    for _ in 0 .. rng.range(1 .. 5) {
+                        line.push(char::from(b'a' + rng.below(26) as u8))
+                    },
+            }
+        }
+        let position = rng.index(line.len() + 1);
+        line.insert(position, digit(rng));
+        line
+    }).collect()
+}
@@ -0,0 +1,18 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// `size` games of one to six draws.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (1 ..= size.max(1)).map(|id| {
+        let draws = (0 .. rng.range(1 .. 7)).map(|_| {
+            let mut colors = COLORS.to_vec();
+            rng.shuffle(&mut colors);
+            colors.truncate(rng.range(1 .. 4) as usize);
+            join(colors.iter().map(|color|
+                format!("{} {color}", rng.range(1 .. 21))), ", ")
+        });
+        format!("Game {id}: {}", join(draws, "; "))
+    }).collect()
+}
@@ -0,0 +1,30 @@
+use aoc_core::rng::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+/// Schematic of `size` x `size` cells. Numbers have one to three digits and
+/// are separated from other numbers on the same line.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(3);
+    (0 .. size).map(|_| {
+        let mut line = String::new();
+        while line.len() < size {
+            let len = rng.range(1 .. 4) as usize;
+            if rng.chance(1, 4) && line.len() + len < size {
+                line.push(char::from(b'0' + rng.range(1 .. 10) as u8));
+                for _ in 1 .. len {
+                    line.push(char::from(b'0' + rng.below(10) as u8));
+                }
+                line.push('.');
+            }
+            else if rng.chance(1, 8) {
+                line.push(*rng.choose(SYMBOLS) as char);
+            }
+            else {
+                line.push('.');
+            }
+        }
+        line.truncate(size);
+        line
+    }).collect()
+}
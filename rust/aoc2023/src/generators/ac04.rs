@@ -0,0 +1,33 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+const WINNING: usize = 10;
+
+const NUMBERS: usize = 25;
+
+fn distinct_numbers(rng: &mut Rng, count: usize) -> Vec<u64> {
+    let mut all: Vec<u64> = (1 .. 100).collect();
+    rng.shuffle(&mut all);
+    all.truncate(count);
+    all
+}
+
+/// `size` scratchcards. A card never wins more copies than there are cards
+/// after it.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    (1 ..= size).map(|id| {
+        let winning = distinct_numbers(rng, WINNING);
+        let max_matches = (size - id).min(WINNING);
+        let matches = rng.index(max_matches + 1).min(rng.index(max_matches + 1));
+        let mut numbers: Vec<u64> = winning[.. matches].to_vec();
+        let mut others: Vec<u64> =
+            (1 .. 100).filter(|n| !winning.contains(n)).collect();
+        rng.shuffle(&mut others);
+        numbers.extend(&others[.. NUMBERS - matches]);
+        rng.shuffle(&mut numbers);
+        let format = |numbers: &[u64]|
+            join(numbers.iter().map(|n| format!("{n:>2}")), " ");
+        format!("Card {id:>3}: {} | {}", format(&winning), format(&numbers))
+    }).collect()
+}
@@ -0,0 +1,38 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+const CATEGORIES: [&str; 8] = [
+    "seed", "soil", "fertilizer", "water", "light", "temperature", "humidity",
+    "location"
+];
+
+const MAX: u64 = 1 << 32;
+
+/// `size` seed ranges and up to `size` lines per map. Each map permutes
+/// consecutive segments of an interval, so it is a bijection.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let seeds: Vec<u64> =
+        (0 .. size).flat_map(|_| [rng.below(MAX), rng.range(1 .. MAX / 64)])
+        .collect();
+    let mut lines = vec![format!("seeds: {}", join(seeds, " "))];
+    for categories in CATEGORIES.windows(2) {
+        lines.push(String::new());
+        lines.push(format!("{}-to-{} map:", categories[0], categories[1]));
+        let mut cuts: Vec<u64> = (0 ..= size).map(|_| rng.below(MAX)).collect();
+        cuts.sort();
+        cuts.dedup();
+        let mut segments: Vec<(u64, u64)> =
+            cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+        rng.shuffle(&mut segments);
+        let mut dst = cuts[0];
+        let mut map_lines: Vec<String> = segments.iter().filter_map(|&(src, len)| {
+            let line = (dst != src).then(|| format!("{dst} {src} {len}"));
+            dst += len;
+            line
+        }).collect();
+        rng.shuffle(&mut map_lines);
+        lines.extend(map_lines);
+    }
+    lines
+}
@@ -0,0 +1,30 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+fn concat(values: &[u64]) -> Option<u64> {
+    join(values, "").parse().ok()
+}
+
+/// `size` races (at most 6, so that the concatenated race of Part 2 fits
+/// in 64 bits). Every race, including the concatenated one, can be won.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let count = size.clamp(1, 6);
+    loop {
+        let times: Vec<u64> = (0 .. count).map(|_| rng.range(10 .. 100)).collect();
+        let distances: Vec<u64> = times.iter().map(|time|
+            rng.below((time / 2) * (time - time / 2))
+        ).collect();
+        let (Some(time), Some(distance)) = (concat(&times), concat(&distances))
+        else { continue };
+        let time = time as u128;
+        if ((time / 2) * (time - time / 2)) <= distance as u128 {
+            continue;
+        }
+        let format = |values: &[u64]|
+            join(values.iter().map(|value| format!("{value:>4}")), " ");
+        return vec![
+            format!("Time:     {}", format(&times)),
+            format!("Distance: {}", format(&distances)),
+        ];
+    }
+}
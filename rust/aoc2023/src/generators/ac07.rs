@@ -0,0 +1,11 @@
+use aoc_core::rng::Rng;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// `size` hands with their bids.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_| {
+        let hand: String = (0 .. 5).map(|_| *rng.choose(CARDS) as char).collect();
+        format!("{hand} {}", rng.range(1 .. 1001))
+    }).collect()
+}
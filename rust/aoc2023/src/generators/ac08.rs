@@ -0,0 +1,64 @@
+use aoc_core::rng::Rng;
+use crate::generators::UniqueNames;
+
+const PRIMES: [usize; 6] = [3, 5, 7, 11, 13, 17];
+
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Instructions of length `size` (at most 150) and one ghost per prime in
+/// `PRIMES`. As in the puzzle inputs, each ghost enters a cycle whose length
+/// is a multiple of the instruction count, and reaches its `Z` node exactly
+/// at the end of each lap. The first ghost goes from `AAA` to `ZZZ`.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let directions: Vec<char> =
+        (0 .. size.clamp(1, 150)).map(|_| *rng.choose(&['L', 'R'])).collect();
+    let mut names = UniqueNames::new(LETTERS, 3);
+    names.reserve("AAA");
+    names.reserve("ZZZ");
+    let mut all_names = Vec::new();
+    let mut ghosts = Vec::new();
+    for (index, prime) in PRIMES.iter().enumerate() {
+        let (start, end) =
+            if index == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            }
+            else {
+                let prefix = names.draw_filtered(rng, |name|
+                    !name.ends_with('A') && !name.ends_with('Z'));
+                let start = format!("{}A", &prefix[.. 2]);
+                let end = format!("{}Z", &prefix[.. 2]);
+                if names.is_used(&start) || names.is_used(&end) {
+                    continue;
+                }
+                names.reserve(&start);
+                names.reserve(&end);
+                (start, end)
+            };
+        // cycle[0] is the end node, cycle[i] is reached after i steps.
+        let mut cycle = vec![end];
+        for _ in 1 .. directions.len() * prime {
+            cycle.push(names.draw_filtered(rng, |name|
+                !name.ends_with('A') && !name.ends_with('Z')));
+        }
+        all_names.extend(cycle.iter().cloned());
+        ghosts.push((start, cycle));
+    }
+    let mut nodes = Vec::new();
+    for (start, cycle) in &ghosts {
+        let mut node = |name: &str, step: usize, next: &str| {
+            let other = rng.choose(&all_names);
+            let (left, right) =
+                if directions[step % directions.len()] == 'L' { (next, other.as_str()) }
+                else { (other.as_str(), next) };
+            format!("{name} = ({left}, {right})")
+        };
+        nodes.push(node(start, 0, &cycle[1 % cycle.len()]));
+        for (position, name) in cycle.iter().enumerate() {
+            nodes.push(node(name, position, &cycle[(position + 1) % cycle.len()]));
+        }
+    }
+    rng.shuffle(&mut nodes);
+    let mut lines = vec![directions.iter().collect(), String::new()];
+    lines.extend(nodes);
+    lines
+}
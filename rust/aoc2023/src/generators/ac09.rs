@@ -0,0 +1,15 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+const LENGTH: i64 = 21;
+
+/// `size` histories, each one being the values of a random polynomial of
+/// degree at most 5 at 0, 1, 2...
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_| {
+        let coefficients: Vec<i64> =
+            (0 ..= rng.range(0 .. 6)).map(|_| rng.range_i64(-5 .. 6)).collect();
+        join((0 .. LENGTH).map(|x|
+            coefficients.iter().rev().fold(0, |accu, c| accu * x + c)), " ")
+    }).collect()
+}
@@ -0,0 +1,121 @@
+use aoc_core::rng::Rng;
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Random spanning tree of an `m` x `m` lattice, drawn as a polyomino where
+/// nodes are the cells with even coordinates and edges the cells between
+/// them. Every node being part of it, two cells never touch only by a
+/// corner, so the contour of the polyomino is a simple loop.
+fn tree_polyomino(rng: &mut Rng, m: usize) -> Vec<Vec<bool>> {
+    let side = 2 * m - 1;
+    let mut cells = vec![vec![false; side]; side];
+    let mut visited = vec![vec![false; m]; m];
+    visited[0][0] = true;
+    cells[0][0] = true;
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    while let Some(&(x, y)) = stack.last() {
+        let next: Vec<(usize, usize)> = DIRECTIONS.iter().filter_map(|&(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < m)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < m)?;
+            (!visited[ny][nx]).then_some((nx, ny))
+        }).collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.choose(&next);
+        visited[ny][nx] = true;
+        cells[y + ny][x + nx] = true;
+        cells[2 * ny][2 * nx] = true;
+        stack.push((nx, ny));
+    }
+    cells
+}
+
+fn pipe((dx1, dy1): (isize, isize), (dx2, dy2): (isize, isize)) -> char {
+    let north = dy1 < 0 || dy2 < 0;
+    let south = dy1 > 0 || dy2 > 0;
+    let east = dx1 > 0 || dx2 > 0;
+    match (north, south, east) {
+        (true, true, _) => '|',
+        (false, false, _) => '-',
+        (true, false, true) => 'L',
+        (true, false, false) => 'J',
+        (false, true, true) => 'F',
+        (false, true, false) => '7',
+    }
+}
+
+/// Repeats each row and each column of `cells` one to three times.
+fn stretch(rng: &mut Rng, cells: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let rows: Vec<usize> = (0 .. cells.len())
+        .flat_map(|y| std::iter::repeat_n(y, rng.range(1 .. 4) as usize)).collect();
+    let columns: Vec<usize> = (0 .. cells.len())
+        .flat_map(|x| std::iter::repeat_n(x, rng.range(1 .. 4) as usize)).collect();
+    let side = rows.len().max(columns.len());
+    (0 .. side).map(|i|
+        (0 .. side).map(|j|
+            rows.get(i).zip(columns.get(j)).is_some_and(|(&y, &x)| cells[y][x])
+        ).collect()
+    ).collect()
+}
+
+/// Square grid with a loop going through `S`, surrounded by scattered pipes
+/// that are not connected to `S`. The loop is the contour of a tree
+/// polyomino of `size` x `size` nodes, stretched so that it encloses some
+/// tiles.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let m = size.max(2);
+    let tree = tree_polyomino(rng, m);
+    let cells = stretch(rng, &tree);
+    let side = cells.len();
+    let is_cell = |x: isize, y: isize|
+        x >= 0 && y >= 0 && (x as usize) < side && (y as usize) < side &&
+        cells[y as usize][x as usize];
+    // Corner (x, y) of the polyomino is at (x + 1, y + 1) in the grid.
+    let mut neighbors = std::collections::HashMap::new();
+    for y in 0 .. side as isize {
+        for x in 0 .. side as isize {
+            if !is_cell(x, y) {
+                continue;
+            }
+            let sides = [
+                (is_cell(x, y - 1), (x, y), (x + 1, y)),
+                (is_cell(x, y + 1), (x, y + 1), (x + 1, y + 1)),
+                (is_cell(x - 1, y), (x, y), (x, y + 1)),
+                (is_cell(x + 1, y), (x + 1, y), (x + 1, y + 1)),
+            ];
+            for (inside, a, b) in sides {
+                if !inside {
+                    neighbors.entry(a).or_insert_with(Vec::new).push(b);
+                    neighbors.entry(b).or_insert_with(Vec::new).push(a);
+                }
+            }
+        }
+    }
+    let grid_side = side + 3;
+    let mut grid: Vec<Vec<char>> = (0 .. grid_side).map(|_|
+        (0 .. grid_side).map(|_| *rng.choose(&['|', '-', 'L', 'J', '7', 'F', '.']))
+        .collect()
+    ).collect();
+    let mut on_loop = vec![vec![false; grid_side]; grid_side];
+    for (&(x, y), corners) in &neighbors {
+        let [(x1, y1), (x2, y2)] = corners[..] else { unreachable!() };
+        let (gx, gy) = (x as usize + 1, y as usize + 1);
+        grid[gy][gx] = pipe((x1 - x, y1 - y), (x2 - x, y2 - y));
+        on_loop[gy][gx] = true;
+    }
+    let mut corners: Vec<_> = neighbors.keys().cloned().collect();
+    corners.sort();
+    let &(x, y) = rng.choose(&corners);
+    let (sx, sy) = (x as usize + 1, y as usize + 1);
+    grid[sy][sx] = 'S';
+    for (dx, dy) in DIRECTIONS {
+        let (nx, ny) =
+            (sx.wrapping_add_signed(dx), sy.wrapping_add_signed(dy));
+        if !on_loop[ny][nx] {
+            grid[ny][nx] = '.';
+        }
+    }
+    grid.iter().map(|line| line.iter().collect()).collect()
+}
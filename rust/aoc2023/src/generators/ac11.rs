@@ -0,0 +1,15 @@
+use aoc_core::rng::Rng;
+
+/// Image of `size` x `size` pixels, where about one row and one column out
+/// of eight are empty.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let empty_rows: Vec<bool> = (0 .. size).map(|_| rng.chance(1, 8)).collect();
+    let empty_columns: Vec<bool> = (0 .. size).map(|_| rng.chance(1, 8)).collect();
+    empty_rows.iter().map(|&empty_row|
+        empty_columns.iter().map(|&empty_column|
+            if !empty_row && !empty_column && rng.chance(1, 12) { '#' }
+            else { '.' }
+        ).collect()
+    ).collect()
+}
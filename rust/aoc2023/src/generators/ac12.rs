@@ -0,0 +1,23 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+/// `size` rows of up to 20 springs, obtained by hiding some of the springs
+/// of a random row with at least one damaged group.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_| {
+        let len = rng.range(1 .. 21) as usize;
+        let mut springs: Vec<char> =
+            (0 .. len).map(|_| if rng.chance(1, 2) { '#' } else { '.' }).collect();
+        if !springs.contains(&'#') {
+            springs[rng.index(len)] = '#';
+        }
+        let groups: Vec<usize> = springs.split(|&c| c == '.')
+            .map(|group| group.len()).filter(|&len| len > 0).collect();
+        for spring in springs.iter_mut() {
+            if rng.chance(1, 2) {
+                *spring = '?';
+            }
+        }
+        format!("{} {}", springs.iter().collect::<String>(), join(groups, ","))
+    }).collect()
+}
@@ -0,0 +1,56 @@
+use aoc_core::rng::Rng;
+
+fn fold(index: usize, axis: usize, len: usize) -> usize {
+    let reach = axis.min(len - axis);
+    if index >= axis && index < axis + reach { 2 * axis - index - 1 } else { index }
+}
+
+/// `size` patterns. Each pattern is symmetric with respect to a vertical
+/// and a horizontal line, then one cell which is out of the reach of the
+/// first line is flipped: the first line is the perfect reflection and the
+/// second one is the reflection with a smudge.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for index in 0 .. size.max(1) {
+        if index > 0 {
+            lines.push(String::new());
+        }
+        let width = rng.range(5 .. 18) as usize;
+        let height = rng.range(5 .. 18) as usize;
+        let mut axis = rng.range(1 .. width as u64) as usize;
+        if 2 * axis == width {
+            axis += 1;
+        }
+        let second_axis = rng.range(1 .. height as u64) as usize;
+        let cells: std::collections::HashMap<(usize, usize), bool> =
+            (0 .. height).flat_map(|y| (0 .. width).map(move |x| (x, y)))
+            .map(|(x, y)| ((x, y), rng.chance(1, 2))).collect();
+        let mut pattern: Vec<Vec<bool>> = (0 .. height).map(|y|
+            (0 .. width).map(|x|
+                cells[&(fold(x, axis, width), fold(y, second_axis, height))]
+            ).collect()
+        ).collect();
+        let reach = axis.min(width - axis);
+        let x =
+            if axis < width - axis {
+                rng.range((2 * axis) as u64 .. width as u64) as usize
+            }
+            else {
+                rng.index(axis - reach)
+            };
+        let second_reach = second_axis.min(height - second_axis);
+        let y = rng.range(
+            (second_axis - second_reach) as u64 ..
+            (second_axis + second_reach) as u64) as usize;
+        pattern[y][x] = !pattern[y][x];
+        if rng.chance(1, 2) {
+            pattern = (0 .. width).map(|x|
+                (0 .. height).map(|y| pattern[y][x]).collect()
+            ).collect();
+        }
+        lines.extend(pattern.iter().map(|row|
+            row.iter().map(|&cell| if cell { '#' } else { '.' }).collect()
+        ));
+    }
+    lines
+}
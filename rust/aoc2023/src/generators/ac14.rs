@@ -0,0 +1,9 @@
+use aoc_core::rng::Rng;
+
+/// Platform of `size` x `size` cells.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_|
+        (0 .. size.max(1)).map(|_| *rng.choose(&['.', '.', '.', 'O', 'O', '#']))
+        .collect()
+    ).collect()
+}
@@ -0,0 +1,17 @@
+use aoc_core::rng::Rng;
+use crate::generators::join;
+
+/// Initialization sequence of `size` steps over a few labels.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let labels: Vec<String> = (0 .. size.div_ceil(4)).map(|_|
+        (0 .. rng.range(2 .. 7)).map(|_| char::from(b'a' + rng.below(26) as u8))
+        .collect()
+    ).collect();
+    let steps = (0 .. size).map(|_| {
+        let label = rng.choose(&labels);
+        if rng.chance(1, 3) { format!("{label}-") }
+        else { format!("{label}={}", rng.range(1 .. 10)) }
+    });
+    vec![join(steps, ",")]
+}
@@ -0,0 +1,10 @@
+use aoc_core::rng::Rng;
+
+/// Contraption of `size` x `size` tiles.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0 .. size.max(1)).map(|_|
+        (0 .. size.max(1)).map(|_|
+            if rng.chance(1, 6) { *rng.choose(&['/', '\\', '|', '-']) } else { '.' }
+        ).collect()
+    ).collect()
+}
@@ -0,0 +1,10 @@
+use aoc_core::rng::Rng;
+
+/// City of `size` x `size` blocks (at least 5 x 5, so that the ultra
+/// crucible can reach the end).
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(5);
+    (0 .. size).map(|_|
+        (0 .. size).map(|_| char::from(b'0' + rng.range(1 .. 10) as u8)).collect()
+    ).collect()
+}
@@ -0,0 +1,43 @@
+use aoc_core::rng::Rng;
+
+const DIRECTIONS: [char; 4] = ['R', 'D', 'L', 'U'];
+
+/// Outline of a histogram with `columns` columns, as `(direction, count)`
+/// moves indexing `DIRECTIONS`: that is always a simple polygon with
+/// `2 * columns + 2` sides.
+fn histogram(rng: &mut Rng, columns: usize, max: u64) -> Vec<(usize, u64)> {
+    let mut heights: Vec<u64> = vec![rng.range(1 .. max)];
+    while heights.len() < columns {
+        let height = rng.range(1 .. max);
+        if Some(&height) != heights.last() {
+            heights.push(height);
+        }
+    }
+    let widths: Vec<u64> = (0 .. columns).map(|_| rng.range(1 .. max)).collect();
+    let mut moves = vec![(3, heights[0])];
+    for i in 0 .. columns {
+        moves.push((0, widths[i]));
+        if i + 1 < columns {
+            let direction = if heights[i + 1] > heights[i] { 3 } else { 1 };
+            moves.push((direction, heights[i + 1].abs_diff(heights[i])));
+        }
+    }
+    moves.push((1, heights[columns - 1]));
+    moves.push((2, widths.iter().sum()));
+    let rotation = rng.index(4);
+    let shift = rng.index(moves.len());
+    moves.rotate_left(shift);
+    moves.into_iter().map(|(direction, count)| ((direction + rotation) % 4, count))
+        .collect()
+}
+
+/// Dig plan of `2 * size + 2` instructions, where both the plan of Part 1
+/// and the plan hidden in the colours are closed loops.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let columns = size.max(1);
+    let part1 = histogram(rng, columns, 11);
+    let part2 = histogram(rng, columns, (0xfffff / columns as u64).max(2));
+    part1.iter().zip(&part2).map(|(&(direction1, count1), &(direction2, count2))|
+        format!("{} {count1} (#{count2:05x}{direction2})", DIRECTIONS[direction1])
+    ).collect()
+}
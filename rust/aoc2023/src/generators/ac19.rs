@@ -0,0 +1,45 @@
+use aoc_core::rng::Rng;
+use crate::generators::UniqueNames;
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+/// Up to `size` workflows forming a tree rooted at `in` (so there is no
+/// cycle), followed by `size` parts.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let mut names = UniqueNames::new("abcdefghijklmnopqrstuvwxyz", 3);
+    names.reserve("in");
+    let mut pending = vec!["in".to_string()];
+    let mut remaining = size - 1;
+    let mut workflows = Vec::new();
+    while let Some(name) = pending.pop() {
+        let mut action = |rng: &mut Rng| {
+            if remaining > 0 && rng.chance(2, 3) {
+                remaining -= 1;
+                let child = names.draw(rng);
+                pending.push(child.clone());
+                child
+            }
+            else {
+                rng.choose(&["A", "R"]).to_string()
+            }
+        };
+        let rules: Vec<String> = (0 .. rng.range(1 .. 4)).map(|_| {
+            let category = rng.choose(&CATEGORIES);
+            let comparison = rng.choose(&['<', '>']);
+            let value = rng.range(1 .. 4001);
+            format!("{category}{comparison}{value}:{}", action(rng))
+        }).collect();
+        let default = action(rng);
+        workflows.push(format!("{name}{{{},{default}}}", rules.join(",")));
+    }
+    rng.shuffle(&mut workflows);
+    let mut lines = workflows;
+    lines.push(String::new());
+    for _ in 0 .. size {
+        let ratings: Vec<String> = CATEGORIES.iter().map(|category|
+            format!("{category}={}", rng.range(1 .. 4001))).collect();
+        lines.push(format!("{{{}}}", ratings.join(",")));
+    }
+    lines
+}
@@ -0,0 +1,47 @@
+use aoc_core::rng::Rng;
+use crate::generators::UniqueNames;
+
+const BITS: usize = 12;
+
+/// `size` counters (at most 8) wired as in the puzzle inputs: each counter
+/// is a chain of `BITS` flip-flops with a conjunction hub which resets the
+/// counter when it reaches a random period. The hubs drive `rx` through
+/// inverters and a final conjunction, so Part 2 is the least common multiple
+/// of the periods.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut names = UniqueNames::new("abcdefghijklmnopqrstuvwxyz", 2);
+    names.reserve("rx");
+    let final_conjunction = names.draw(rng);
+    let mut lines = Vec::new();
+    let mut starts = Vec::new();
+    for _ in 0 .. size.clamp(1, 8) {
+        let period = rng.range(1 << (BITS - 1) .. 1 << BITS) | 1;
+        let flip_flops: Vec<String> = (0 .. BITS).map(|_| names.draw(rng)).collect();
+        let hub = names.draw(rng);
+        let inverter = names.draw(rng);
+        let mut hub_destinations = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut destinations = Vec::new();
+            if let Some(next) = flip_flops.get(bit + 1) {
+                destinations.push(next.clone());
+            }
+            if period & (1 << bit) != 0 {
+                destinations.push(hub.clone());
+            }
+            else {
+                hub_destinations.push(flip_flop.clone());
+            }
+            rng.shuffle(&mut destinations);
+            lines.push(format!("%{flip_flop} -> {}", destinations.join(", ")));
+        }
+        hub_destinations.push(inverter.clone());
+        rng.shuffle(&mut hub_destinations);
+        lines.push(format!("&{hub} -> {}", hub_destinations.join(", ")));
+        lines.push(format!("&{inverter} -> {final_conjunction}"));
+        starts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines.push(format!("&{final_conjunction} -> rx"));
+    rng.shuffle(&mut lines);
+    lines
+}
@@ -0,0 +1,39 @@
+use aoc_core::rng::Rng;
+
+/// Sides for which 26501365 steps reach the middle of a copy of the map, as
+/// Part 2 requires.
+const SIDES: [usize; 2] = [131, 393];
+
+/// Garden of odd `side` with `S` in the middle. As in the puzzle inputs,
+/// the middle row and column, the border and a diamond joining the middles
+/// of the borders have no rocks.
+pub fn garden(rng: &mut Rng, side: usize) -> Vec<String> {
+    let center = side / 2;
+    (0 .. side).map(|y|
+        (0 .. side).map(|x| {
+            let distance = x.abs_diff(center) + y.abs_diff(center);
+            if x == center && y == center {
+                'S'
+            }
+            else if
+                x == center || y == center || x == 0 || y == 0 ||
+                x == side - 1 || y == side - 1 || distance.abs_diff(center) <= 1
+            {
+                '.'
+            }
+            else if rng.chance(1, 6) {
+                '#'
+            }
+            else {
+                '.'
+            }
+        }).collect()
+    ).collect()
+}
+
+/// Garden whose side is the smallest of `SIDES` which is at least `size`.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let side =
+        SIDES.iter().cloned().find(|&side| side >= size).unwrap_or(SIDES[1]);
+    garden(rng, side)
+}
@@ -0,0 +1,34 @@
+use aoc_core::rng::Rng;
+
+const WIDTH: u64 = 10;
+
+/// `size` bricks of at most 4 cubes in a 10 x 10 column, which do not
+/// intersect each other.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let height = 2 * size as u64 + 10;
+    let mut occupied = std::collections::HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < size {
+        let inf = [rng.below(WIDTH), rng.below(WIDTH), rng.range(1 .. height)];
+        let mut sup = inf;
+        let axis = rng.index(3);
+        sup[axis] += rng.below(4);
+        if axis < 2 && sup[axis] >= WIDTH {
+            continue;
+        }
+        let cubes: Vec<[u64; 3]> = (0 ..= sup[axis] - inf[axis]).map(|i| {
+            let mut cube = inf;
+            cube[axis] += i;
+            cube
+        }).collect();
+        if cubes.iter().any(|cube| occupied.contains(cube)) {
+            continue;
+        }
+        occupied.extend(cubes);
+        lines.push(format!(
+            "{},{},{}~{},{},{}", inf[0], inf[1], inf[2], sup[0], sup[1], sup[2]
+        ));
+    }
+    lines
+}
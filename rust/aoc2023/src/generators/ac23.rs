@@ -0,0 +1,43 @@
+use aoc_core::rng::Rng;
+
+fn positions(rng: &mut Rng, count: usize) -> Vec<usize> {
+    let mut positions = vec![1];
+    while positions.len() < count {
+        positions.push(positions[positions.len() - 1] + rng.range(3 .. 10) as usize);
+    }
+    positions
+}
+
+/// Maze whose intersections form a `size` x `size` lattice (with `size`
+/// between 2 and 6, so that the longest path stays tractable), joined by
+/// corridors of random lengths. Slopes at both ends of the corridors point
+/// right or down, so Part 1 sees a directed acyclic graph.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let count = size.clamp(2, 6);
+    let xs = positions(rng, count);
+    let ys = positions(rng, count);
+    let width = xs[count - 1] + 2;
+    let height = ys[count - 1] + 2;
+    let mut grid = vec![vec!['#'; width]; height];
+    grid[0][1] = '.';
+    grid[height - 1][width - 2] = '.';
+    for &y in &ys {
+        for i in 0 .. count - 1 {
+            for cell in &mut grid[y][xs[i] ..= xs[i + 1]] {
+                *cell = '.';
+            }
+            grid[y][xs[i] + 1] = '>';
+            grid[y][xs[i + 1] - 1] = '>';
+        }
+    }
+    for &x in &xs {
+        for j in 0 .. count - 1 {
+            for line in &mut grid[ys[j] ..= ys[j + 1]] {
+                line[x] = '.';
+            }
+            grid[ys[j] + 1][x] = 'v';
+            grid[ys[j + 1] - 1][x] = 'v';
+        }
+    }
+    grid.iter().map(|line| line.iter().collect()).collect()
+}
@@ -0,0 +1,25 @@
+use aoc_core::rng::Rng;
+
+/// `size` hailstones (at least 5) that a rock thrown from an integer
+/// position with an integer velocity hits at distinct integer times.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let rock_position: [i64; 3] = std::array::from_fn(|_|
+        rng.range_i64(250_000_000_000_000 .. 350_000_000_000_000));
+    let rock_velocity: [i64; 3] = std::array::from_fn(|_| rng.range_i64(-200 .. 201));
+    let mut times = std::collections::HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < size.max(5) {
+        let time = rng.range_i64(100_000_000_000 .. 1_000_000_000_000);
+        let velocity: [i64; 3] = std::array::from_fn(|_| rng.range_i64(-300 .. 301));
+        if velocity == rock_velocity || !times.insert(time) {
+            continue;
+        }
+        let position: [i64; 3] = std::array::from_fn(|i|
+            rock_position[i] + (rock_velocity[i] - velocity[i]) * time);
+        lines.push(format!(
+            "{}, {}, {} @ {}, {}, {}", position[0], position[1], position[2],
+            velocity[0], velocity[1], velocity[2]
+        ));
+    }
+    lines
+}
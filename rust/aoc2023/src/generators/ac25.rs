@@ -0,0 +1,46 @@
+use aoc_core::rng::Rng;
+use crate::generators::UniqueNames;
+
+/// Random graph on `count` nodes (at least 5) which is 4-edge-connected: a
+/// complete graph on 5 nodes, then each other node is linked to 4 distinct
+/// previous nodes.
+fn component(rng: &mut Rng, count: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for node in 1 .. count {
+        let mut previous: Vec<usize> = (0 .. node).collect();
+        rng.shuffle(&mut previous);
+        edges.extend(previous.into_iter().take(4).map(|other| (node, other)));
+    }
+    edges
+}
+
+/// Graph of `size` nodes (at least 10) made of two 4-edge-connected
+/// components joined by 3 edges, which is therefore the only cut of size 3.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(10);
+    let left = rng.range(5 .. size as u64 - 4) as usize;
+    let mut edges = component(rng, left);
+    edges.extend(component(rng, size - left).into_iter()
+        .map(|(u, v)| (u + left, v + left)));
+    let mut cut = Vec::new();
+    while cut.len() < 3 {
+        let edge = (rng.index(left), left + rng.index(size - left));
+        if !cut.contains(&edge) {
+            cut.push(edge);
+        }
+    }
+    edges.extend(cut);
+    let mut names = UniqueNames::new("abcdefghijklmnopqrstuvwxyz", 3);
+    let names: Vec<String> = (0 .. size).map(|_| names.draw(rng)).collect();
+    let mut adjacency = vec![Vec::new(); size];
+    for (u, v) in edges {
+        let (u, v) = if rng.chance(1, 2) { (u, v) } else { (v, u) };
+        adjacency[u].push(names[v].clone());
+    }
+    let mut lines: Vec<String> = adjacency.iter().enumerate()
+        .filter(|(_node, targets)| !targets.is_empty())
+        .map(|(node, targets)| format!("{}: {}", names[node], targets.join(" ")))
+        .collect();
+    rng.shuffle(&mut lines);
+    lines
+}
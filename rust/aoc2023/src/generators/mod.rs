@@ -0,0 +1,113 @@
+//! Random puzzle-input generators, one module per day.
+//!
+//! Each generator produces a valid input whose size grows with `size`; what
+//! `size` counts (lines, games, grid side...) depends on the day. The same
+//! seed always gives the same input.
+
+use aoc_core::{Result, rng::Rng};
+
+pub mod ac01;
+pub mod ac02;
+pub mod ac03;
+pub mod ac04;
+pub mod ac05;
+pub mod ac06;
+pub mod ac07;
+pub mod ac08;
+pub mod ac09;
+pub mod ac10;
+pub mod ac11;
+pub mod ac12;
+pub mod ac13;
+pub mod ac14;
+pub mod ac15;
+pub mod ac16;
+pub mod ac17;
+pub mod ac18;
+pub mod ac19;
+pub mod ac20;
+pub mod ac21;
+pub mod ac22;
+pub mod ac23;
+pub mod ac24;
+pub mod ac25;
+
+pub fn generate(day: u32, seed: u64, size: usize) -> Result<String> {
+    let generator: fn(&mut Rng, usize) -> Vec<String> =
+        match day {
+            1 => ac01::generate,
+            2 => ac02::generate,
+            3 => ac03::generate,
+            4 => ac04::generate,
+            5 => ac05::generate,
+            6 => ac06::generate,
+            7 => ac07::generate,
+            8 => ac08::generate,
+            9 => ac09::generate,
+            10 => ac10::generate,
+            11 => ac11::generate,
+            12 => ac12::generate,
+            13 => ac13::generate,
+            14 => ac14::generate,
+            15 => ac15::generate,
+            16 => ac16::generate,
+            17 => ac17::generate,
+            18 => ac18::generate,
+            19 => ac19::generate,
+            20 => ac20::generate,
+            21 => ac21::generate,
+            22 => ac22::generate,
+            23 => ac23::generate,
+            24 => ac24::generate,
+            25 => ac25::generate,
+            _ => Err(format!("No generator for day {day}"))?,
+        };
+    let mut rng = Rng::new(seed);
+    let mut input = generator(&mut rng, size).join("\n");
+    input.push('\n');
+    Ok(input)
+}
+
+/// Draws names from `alphabet` that were not drawn before.
+pub struct UniqueNames {
+    alphabet: &'static [u8],
+    len: usize,
+    used: std::collections::HashSet<String>,
+}
+
+impl UniqueNames {
+    pub fn new(alphabet: &'static str, len: usize) -> Self {
+        Self {
+            alphabet: alphabet.as_bytes(), len,
+            used: std::collections::HashSet::new(),
+        }
+    }
+
+    pub fn reserve(&mut self, name: &str) {
+        self.used.insert(name.to_string());
+    }
+
+    pub fn is_used(&self, name: &str) -> bool {
+        self.used.contains(name)
+    }
+
+    pub fn draw(&mut self, rng: &mut Rng) -> String {
+        self.draw_filtered(rng, |_| true)
+    }
+
+    pub fn draw_filtered(
+        &mut self, rng: &mut Rng, accept: impl Fn(&str) -> bool
+    ) -> String {
+        loop {
+            let name: String = (0 .. self.len)
+                .map(|_| *rng.choose(self.alphabet) as char).collect();
+            if accept(&name) && self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+pub fn join<T: ToString>(items: impl IntoIterator<Item = T>, sep: &str) -> String {
+    items.into_iter().map(|item| item.to_string()).collect::<Vec<_>>().join(sep)
+}
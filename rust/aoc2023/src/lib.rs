@@ -0,0 +1,7 @@
+pub mod generators;
+
+pub mod days;
+
+pub mod reference;
+
+pub mod differential;
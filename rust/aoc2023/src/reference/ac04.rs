@@ -0,0 +1,31 @@
+use aoc_core::Result;
+
+pub fn matches(input: &str) -> Result<Vec<usize>> {
+    input.lines().map(|line| {
+        let (_, contents) = line.split_once(':').ok_or("Missing ':'")?;
+        let (winning_str, numbers_str) =
+            contents.split_once('|').ok_or("Missing '|'")?;
+        let winning: Vec<u64> = winning_str.split_whitespace()
+            .map(|s| s.parse()).collect::<std::result::Result<_, _>>()?;
+        let numbers: Vec<u64> = numbers_str.split_whitespace()
+            .map(|s| s.parse()).collect::<std::result::Result<_, _>>()?;
+        Ok(numbers.iter().filter(|number| winning.contains(number)).count())
+    }).collect()
+}
+
+pub fn part1(matches: &[usize]) -> usize {
+    matches.iter().map(|&count|
+        if count == 0 { 0 } else { 2_usize.pow(count as u32 - 1) }
+    ).sum()
+}
+
+/// Each card adds its number of copies to each of the next `count` cards.
+pub fn part2(matches: &[usize]) -> usize {
+    let mut copies = vec![1; matches.len()];
+    for (index, &count) in matches.iter().enumerate() {
+        for next in index + 1 ..= (index + count).min(matches.len() - 1) {
+            copies[next] += copies[index];
+        }
+    }
+    copies.iter().sum()
+}
@@ -0,0 +1,43 @@
+use aoc_core::Result;
+
+fn numbers(line: Option<&str>, prefix: &str) -> Result<Vec<String>> {
+    let numbers_str = line.and_then(|line| line.strip_prefix(prefix))
+        .ok_or(format!("Missing '{prefix}' line"))?;
+    Ok(numbers_str.split_whitespace().map(|s| s.to_string()).collect())
+}
+
+/// Races as (time, distance) pairs: the races of Part 1 followed by the
+/// race of Part 2, obtained by concatenating the digits.
+pub fn races(input: &str) -> Result<Vec<(u64, u64)>> {
+    let mut lines = input.lines();
+    let times = numbers(lines.next(), "Time:")?;
+    let distances = numbers(lines.next(), "Distance:")?;
+    if times.len() != distances.len() {
+        Err("Times and distances differ in number")?
+    }
+    let mut races = times.iter().zip(&distances)
+        .map(|(time, distance)| Ok((time.parse()?, distance.parse()?)))
+        .collect::<Result<Vec<_>>>()?;
+    races.push((times.concat().parse()?, distances.concat().parse()?));
+    Ok(races)
+}
+
+/// Tries every holding time.
+pub fn number_ways(time: u64, distance: u64) -> u64 {
+    (0 ..= time).filter(|hold| (time - hold) * hold > distance).count() as u64
+}
+
+/// The former closed form in floating point, only exact while the values
+/// fit in the 53 bits of the mantissa.
+pub fn number_ways_float(time: u64, distance: u64) -> u64 {
+    let time = time as f64;
+    let distance = distance as f64;
+    let delta = time.powf(2.) - 4.0 * distance;
+    let sqrt_delta = delta.sqrt();
+    let alpha = - (- time + sqrt_delta) / 2.;
+    let mut min_holding_time = alpha.ceil();
+    if min_holding_time == alpha {
+        min_holding_time += 1.;
+    }
+    (time - 2. * min_holding_time) as u64 + 1
+}
@@ -0,0 +1,40 @@
+use aoc_core::Result;
+
+/// Sum of the distances between galaxies, computed by actually inserting
+/// `expansion - 1` copies of each empty row and column into the image.
+pub fn distances(input: &str, expansion: usize) -> Result<u64> {
+    let grid: Vec<Vec<char>> =
+        input.lines().map(|line| line.chars().collect()).collect();
+    let width = grid.first().ok_or("Empty image")?.len();
+    if grid.iter().any(|line| line.len() != width) {
+        Err("Ragged image")?
+    }
+    let empty_columns: Vec<bool> = (0 .. width).map(|x|
+        grid.iter().all(|line| line[x] != '#')
+    ).collect();
+    let mut expanded = Vec::new();
+    for line in &grid {
+        let mut expanded_line = Vec::new();
+        for (x, &c) in line.iter().enumerate() {
+            let copies = if empty_columns[x] { expansion } else { 1 };
+            expanded_line.extend(std::iter::repeat_n(c, copies));
+        }
+        let copies = if line.contains(&'#') { 1 } else { expansion };
+        expanded.extend(std::iter::repeat_n(expanded_line, copies));
+    }
+    let mut galaxies = Vec::new();
+    for (y, line) in expanded.iter().enumerate() {
+        for (x, &c) in line.iter().enumerate() {
+            if c == '#' {
+                galaxies.push((x as i64, y as i64));
+            }
+        }
+    }
+    let mut total = 0;
+    for (i, &(x1, y1)) in galaxies.iter().enumerate() {
+        for &(x2, y2) in &galaxies[i + 1 ..] {
+            total += x1.abs_diff(x2) + y1.abs_diff(y2);
+        }
+    }
+    Ok(total)
+}
@@ -0,0 +1,42 @@
+use aoc_core::Result;
+
+pub const MAX_UNKNOWNS: usize = 20;
+
+fn groups(springs: &[u8]) -> Vec<usize> {
+    springs.split(|&c| c == b'.').map(|group| group.len())
+        .filter(|&len| len > 0).collect()
+}
+
+/// Tries every assignment of the unknown springs.
+pub fn count_arrangements(springs: &str, expected: &[usize]) -> Result<u64> {
+    let mut springs = springs.as_bytes().to_vec();
+    if let Some(c) = springs.iter().find(|c| !b".#?".contains(c)) {
+        Err(format!("Unknown symbol {}", *c as char))?
+    }
+    let unknowns: Vec<usize> = springs.iter().enumerate()
+        .filter(|&(_, &c)| c == b'?').map(|(index, _)| index).collect();
+    if unknowns.len() > MAX_UNKNOWNS {
+        Err("Too many unknown springs")?
+    }
+    let mut count = 0;
+    for mask in 0 .. 1_u64 << unknowns.len() {
+        for (bit, &index) in unknowns.iter().enumerate() {
+            springs[index] = if mask >> bit & 1 == 1 { b'#' } else { b'.' };
+        }
+        if groups(&springs) == expected {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Sum of the arrangements of the rows, each row being first unfolded
+/// `times` times.
+pub fn total(input: &str, times: usize) -> Result<u64> {
+    input.lines().map(|line| {
+        let (springs, groups_str) = line.split_once(' ').ok_or("Missing ' '")?;
+        let groups: Vec<usize> = groups_str.split(',').map(|s| s.parse())
+            .collect::<std::result::Result<_, _>>()?;
+        count_arrangements(&vec![springs; times].join("?"), &groups.repeat(times))
+    }).sum()
+}
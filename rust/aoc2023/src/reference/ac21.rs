@@ -0,0 +1,35 @@
+use aoc_core::Result;
+
+/// Number of plots reachable in exactly `steps` steps: the plots at
+/// distance at most `steps` with the same parity, since the gardener can
+/// always step back and forth. Distances come from a breadth-first search,
+/// on the map repeated infinitely if `infinite`.
+pub fn reachable(input: &str, steps: usize, infinite: bool) -> Result<usize> {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let height = grid.len() as i64;
+    let width = grid.first().ok_or("Empty map")?.len() as i64;
+    let start = grid.iter().enumerate().find_map(|(y, line)|
+        line.iter().position(|&c| c == b'S').map(|x| (x as i64, y as i64))
+    ).ok_or("No gardener found")?;
+    let is_plot = |(x, y): (i64, i64)| {
+        if !infinite && (x < 0 || y < 0 || x >= width || y >= height) {
+            return false;
+        }
+        grid[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize] != b'#'
+    };
+    let mut distances = std::collections::HashMap::from([(start, 0)]);
+    let mut queue = std::collections::VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        let distance = distances[&(x, y)];
+        if distance == steps {
+            continue;
+        }
+        for next in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+            if is_plot(next) && !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(distances.values().filter(|&&distance| distance % 2 == steps % 2).count())
+}
@@ -0,0 +1,79 @@
+use aoc_core::Result;
+
+fn find(parents: &mut [usize], node: usize) -> usize {
+    let mut root = node;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[node] = root;
+    root
+}
+
+/// Sizes of the connected components of the graph without the edges in
+/// `removed`.
+fn components(nodes: usize, edges: &[(usize, usize)], removed: &[usize]) -> Vec<usize> {
+    let mut parents: Vec<usize> = (0 .. nodes).collect();
+    for (index, &(u, v)) in edges.iter().enumerate() {
+        if !removed.contains(&index) {
+            let (u, v) = (find(&mut parents, u), find(&mut parents, v));
+            parents[u] = v;
+        }
+    }
+    let mut sizes = std::collections::HashMap::new();
+    for node in 0 .. nodes {
+        *sizes.entry(find(&mut parents, node)).or_insert(0) += 1;
+    }
+    sizes.into_values().collect()
+}
+
+/// Calls `f` on every subset of `count` indices below `len`.
+fn subsets(len: usize, count: usize, prefix: &mut Vec<usize>, f: &mut impl FnMut(&[usize])) {
+    if prefix.len() == count {
+        f(prefix);
+        return;
+    }
+    let start = prefix.last().map_or(0, |last| last + 1);
+    for index in start .. len {
+        prefix.push(index);
+        subsets(len, count, prefix, f);
+        prefix.pop();
+    }
+}
+
+/// Product of the sizes of the two groups separated by the minimum edge
+/// cut, found by trying every set of 1, 2, then 3 edges. Fails if the
+/// minimum cut is larger or if several minimum cuts give different
+/// products.
+pub fn minimum_cut_product(input: &str) -> Result<usize> {
+    let mut names = std::collections::HashMap::new();
+    let mut edges = Vec::new();
+    for line in input.lines() {
+        let (source, targets) = line.split_once(": ").ok_or("No ': ' found")?;
+        for target in targets.split(' ') {
+            let next = names.len();
+            let u = *names.entry(source).or_insert(next);
+            let next = names.len();
+            let v = *names.entry(target).or_insert(next);
+            edges.push((u.min(v), u.max(v)));
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    if components(names.len(), &edges, &[]).len() != 1 {
+        Err("Graph is not connected")?
+    }
+    for count in 1 ..= 3 {
+        let mut products = std::collections::BTreeSet::new();
+        subsets(edges.len(), count, &mut Vec::new(), &mut |removed| {
+            if let &[a, b] = &components(names.len(), &edges, removed)[..] {
+                products.insert(a * b);
+            }
+        });
+        match products.len() {
+            0 => (),
+            1 => return Ok(products.into_iter().next().unwrap_or_default()),
+            _ => Err("Several minimum cuts")?,
+        }
+    }
+    Err("No cut of at most 3 edges")?
+}
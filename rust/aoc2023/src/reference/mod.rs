@@ -0,0 +1,9 @@
+//! Slow but straightforward solvers, used to cross-check the fast ones (see
+//! `crate::differential`). They only handle small inputs.
+
+pub mod ac04;
+pub mod ac06;
+pub mod ac11;
+pub mod ac12;
+pub mod ac21;
+pub mod ac25;